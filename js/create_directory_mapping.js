@@ -8,11 +8,13 @@
         const sub_file_name = get_sub_file_name(folder_prefix, file_name);
 
         if (is_nested_object(file_contents)) {
+            directory_mapping.set(`${sub_file_name}/`, {data: new Uint8Array(0), directory: true});
+
             const sub_directory_mapping = create_directory_mapping(file_contents, sub_file_name);
             directory_mapping = new Map([...directory_mapping, ...sub_directory_mapping])
         } else {
-            let file_byte_array = get_file_contents_byte_array(textEncoder, file_contents);
-            directory_mapping.set(sub_file_name, file_byte_array);
+            let file_descriptor = get_file_descriptor(textEncoder, file_contents);
+            directory_mapping.set(sub_file_name, file_descriptor);
         }
     }
 
@@ -20,7 +22,39 @@
 }
 
 function is_nested_object(file_contents) {
-    return typeof file_contents === "object" && !(file_contents instanceof Uint8Array);
+    return typeof file_contents === "object" && !is_file_data(file_contents) && !is_file_descriptor(file_contents);
+}
+
+function is_file_data(file_contents) {
+    return file_contents instanceof Uint8Array || typeof file_contents == "string";
+}
+
+const FILE_DESCRIPTOR_KEYS = [
+    "data", "compression", "level", "type", "lastModified", "lastAccessed", "created", "mode", "uid", "gid", "symlink",
+    "localExtraFields", "centralExtraFields", "comment"
+];
+
+//A folder may hold files named like descriptor properties, so only an object with data and at least one other property,
+//all of them known, is read as a file descriptor
+function is_file_descriptor(file_contents) {
+    if (file_contents === null || typeof file_contents !== "object" || !is_file_data(file_contents.data)) {
+        return false;
+    }
+
+    const keys = Object.keys(file_contents);
+
+    return keys.length > 1 && keys.every(key => FILE_DESCRIPTOR_KEYS.includes(key));
+}
+
+function get_file_descriptor(textEncoder, file_contents) {
+    if (is_file_descriptor(file_contents)) {
+        return {
+            ...file_contents,
+            data: get_file_contents_byte_array(textEncoder, file_contents.data)
+        };
+    }
+
+    return {data: get_file_contents_byte_array(textEncoder, file_contents)};
 }
 
 function get_file_contents_byte_array(textEncoder, file_contents) {
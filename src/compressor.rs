@@ -0,0 +1,15 @@
+pub mod deflate_compressor_adapter;
+pub mod deflate_stream_compressor_adapter;
+
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
+pub const MAXIMUM_COMPRESSION_LEVEL: u8 = 9;
+
+pub trait Compressor {
+    fn compress(&self, data: &[u8], level: u8) -> Vec<u8>;
+}
+
+//Chunks are compressed as they arrive, and whatever the compressor still holds back is returned by finish
+pub trait StreamCompressor {
+    fn compress_chunk(&mut self, data: &[u8]) -> Vec<u8>;
+    fn finish(&mut self) -> Vec<u8>;
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use miniz_oxide::deflate::compress_to_vec;
+
+use crate::compressor::Compressor;
+
+pub struct DeflateCompressorAdapter {}
+
+impl Compressor for DeflateCompressorAdapter {
+    fn compress(&self, data: &[u8], level: u8) -> Vec<u8> {
+        compress_to_vec(data, level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miniz_oxide::inflate::decompress_to_vec;
+
+    use super::*;
+
+    #[test]
+    fn compressed_data_inflates_to_original() {
+        let input_bytes = "Capoo is Hungry. Capoo is Hungry. Capoo is Hungry.".as_bytes();
+
+        let compressor = DeflateCompressorAdapter {};
+
+        let compressed_bytes = compressor.compress(input_bytes, 6);
+
+        assert_eq!(input_bytes, decompress_to_vec(&compressed_bytes).unwrap().as_slice());
+    }
+
+    #[test]
+    fn repetitive_data_shrinks() {
+        let input_bytes: &[u8] = &[0x42; 1000];
+
+        let compressor = DeflateCompressorAdapter {};
+
+        let compressed_bytes = compressor.compress(input_bytes, 6);
+
+        assert!(compressed_bytes.len() < input_bytes.len());
+    }
+
+    #[test]
+    fn empty_input() {
+        let compressor = DeflateCompressorAdapter {};
+
+        let compressed_bytes = compressor.compress(&[], 6);
+
+        assert_eq!(Vec::<u8>::new(), decompress_to_vec(&compressed_bytes).unwrap());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use miniz_oxide::deflate::core::{compress, create_comp_flags_from_zip_params, CompressorOxide, TDEFLFlush, TDEFLStatus};
+
+use crate::compressor::StreamCompressor;
+
+const OUTPUT_BUFFER_SIZE: usize = 0x8000;
+
+pub struct DeflateStreamCompressorAdapter {
+    pub(crate) compressor: Box<CompressorOxide>,
+}
+
+impl DeflateStreamCompressorAdapter {
+    //The flags match compress_to_vec, which writes raw deflate data without a zlib header
+    pub fn new(level: u8) -> DeflateStreamCompressorAdapter {
+        DeflateStreamCompressorAdapter {
+            compressor: Box::new(CompressorOxide::new(create_comp_flags_from_zip_params(level.into(), 0, 0))),
+        }
+    }
+
+    fn deflate(&mut self, mut data: &[u8], flush: TDEFLFlush) -> Vec<u8> {
+        let mut compressed_data: Vec<u8> = Vec::new();
+        let mut output_buffer = vec![0; OUTPUT_BUFFER_SIZE];
+
+        loop {
+            let (status, bytes_in, bytes_out) = compress(&mut self.compressor, data, &mut output_buffer, flush);
+
+            compressed_data.extend_from_slice(&output_buffer[..bytes_out]);
+            data = &data[bytes_in..];
+
+            match status {
+                TDEFLStatus::Done => return compressed_data,
+                TDEFLStatus::Okay if flush == TDEFLFlush::None && data.is_empty() && bytes_out < OUTPUT_BUFFER_SIZE => return compressed_data,
+                TDEFLStatus::Okay => continue,
+                _ => panic!("The deflate stream could not be compressed"),
+            }
+        }
+    }
+}
+
+impl StreamCompressor for DeflateStreamCompressorAdapter {
+    fn compress_chunk(&mut self, data: &[u8]) -> Vec<u8> {
+        self.deflate(data, TDEFLFlush::None)
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        self.deflate(&[], TDEFLFlush::Finish)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miniz_oxide::deflate::compress_to_vec;
+
+    use super::*;
+
+    #[test]
+    fn chunked_data_compresses_like_the_whole_data() {
+        let input_bytes = "Capoo is Hungry. ".repeat(5000);
+
+        let mut stream_compressor = DeflateStreamCompressorAdapter::new(6);
+
+        let mut compressed_bytes: Vec<u8> = Vec::new();
+
+        for chunk in input_bytes.as_bytes().chunks(1000) {
+            compressed_bytes.extend(stream_compressor.compress_chunk(chunk));
+        }
+
+        compressed_bytes.extend(stream_compressor.finish());
+
+        assert_eq!(compress_to_vec(input_bytes.as_bytes(), 6), compressed_bytes);
+    }
+
+    #[test]
+    fn empty_stream() {
+        let mut stream_compressor = DeflateStreamCompressorAdapter::new(6);
+
+        assert_eq!(compress_to_vec(&[], 6), stream_compressor.finish());
+    }
+}
\ No newline at end of file
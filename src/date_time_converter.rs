@@ -1,5 +1,8 @@
 pub mod dos_date_time_calculator_adapter;
 
+const SECONDS_PER_DAY: i64 = 86400;
+const MAXIMUM_UNIX_TIMESTAMP: i64 = 253402300799;
+
 pub struct SystemTime {
     pub hours: u16,
     pub minutes: u16,
@@ -9,7 +12,185 @@ pub struct SystemTime {
     pub year: u16,
 }
 
+impl SystemTime {
+    //Converts seconds since the Unix epoch to a UTC date and time, clamped between 1970 and the end of 9999
+    pub fn from_unix_timestamp(timestamp: i64) -> SystemTime {
+        let timestamp = timestamp.clamp(0, MAXIMUM_UNIX_TIMESTAMP);
+        let seconds_of_day = timestamp % SECONDS_PER_DAY;
+
+        //Civil date from days since the epoch, counting in 400 year eras that start on the 1st of March
+        let days_since_march_2000 = timestamp / SECONDS_PER_DAY - 11017;
+        let era = days_since_march_2000.div_euclid(146097);
+        let day_of_era = days_since_march_2000.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = 2000 + era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+        SystemTime {
+            hours: (seconds_of_day / 3600) as u16,
+            minutes: (seconds_of_day % 3600 / 60) as u16,
+            seconds: (seconds_of_day % 60) as u16,
+            day: day as u16,
+            month: month as u16,
+            year: year as u16,
+        }
+    }
+
+    pub fn from_unix_time_millis(unix_time_millis: i64) -> SystemTime {
+        SystemTime::from_unix_timestamp(unix_time_millis.div_euclid(1000))
+    }
+
+    pub fn from_dos_date_time(dos_date: u16, dos_time: u16) -> SystemTime {
+        SystemTime {
+            hours: dos_time >> 11,
+            minutes: (dos_time >> 5) & 0x3F,
+            seconds: (dos_time & 0x1F) * 2,
+            day: dos_date & 0x1F,
+            month: (dos_date >> 5) & 0x0F,
+            year: (dos_date >> 9) + 1980,
+        }
+    }
+
+    //The inverse of from_unix_timestamp, where a day or month of zero is treated as the first
+    pub fn to_unix_time_millis(&self) -> i64 {
+        let month = i64::from(self.month.clamp(1, 12));
+        let day = i64::from(self.day.max(1));
+        let year = i64::from(self.year) - if month <= 2 { 1 } else { 0 };
+
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days_since_epoch = era * 146097 + day_of_era - 719468;
+
+        let seconds_of_day = i64::from(self.hours) * 3600 + i64::from(self.minutes) * 60 + i64::from(self.seconds);
+
+        (days_since_epoch * SECONDS_PER_DAY + seconds_of_day) * 1000
+    }
+
+    //Reads the date and time in the browser's time zone, the counterpart of how archives are stamped by default
+    #[cfg(target_arch = "wasm32")]
+    pub fn to_local_unix_time_millis(&self) -> i64 {
+        js_sys::Date::new_with_year_month_day_hr_min_sec(
+            u32::from(self.year),
+            i32::from(self.month) - 1,
+            i32::from(self.day),
+            i32::from(self.hours),
+            i32::from(self.minutes),
+            i32::from(self.seconds),
+        ).get_time() as i64
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn to_local_unix_time_millis(&self) -> i64 {
+        self.to_unix_time_millis()
+    }
+}
+
+//Browsers do not provide the system clock through the standard library, so the time is read from JavaScript instead
+#[cfg(target_arch = "wasm32")]
+pub fn get_current_unix_time_millis() -> i64 {
+    js_sys::Date::now().floor() as i64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_current_unix_time_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
 pub trait DosDateTimeCalculator {
     fn get_current_dos_time(&self) -> u16;
     fn get_current_dos_date(&self) -> u16;
+    fn get_current_unix_time_millis(&self) -> i64;
+    fn get_date_time(&self, unix_time_millis: i64) -> SystemTime;
+    fn get_dos_time(&self, date_time: &SystemTime) -> u16;
+    fn get_dos_date(&self, date_time: &SystemTime) -> u16;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_time_from_unix_epoch() {
+        let system_time = SystemTime::from_unix_timestamp(0);
+
+        assert_eq!(0, system_time.hours);
+        assert_eq!(0, system_time.minutes);
+        assert_eq!(0, system_time.seconds);
+        assert_eq!(1, system_time.day);
+        assert_eq!(1, system_time.month);
+        assert_eq!(1970, system_time.year);
+    }
+
+    #[test]
+    fn system_time_from_unix_timestamp() {
+        let system_time = SystemTime::from_unix_timestamp(1654424430);
+
+        assert_eq!(10, system_time.hours);
+        assert_eq!(20, system_time.minutes);
+        assert_eq!(30, system_time.seconds);
+        assert_eq!(5, system_time.day);
+        assert_eq!(6, system_time.month);
+        assert_eq!(2022, system_time.year);
+    }
+
+    #[test]
+    fn system_time_from_unix_timestamp_on_leap_day() {
+        let system_time = SystemTime::from_unix_timestamp(951868799);
+
+        assert_eq!(23, system_time.hours);
+        assert_eq!(59, system_time.minutes);
+        assert_eq!(59, system_time.seconds);
+        assert_eq!(29, system_time.day);
+        assert_eq!(2, system_time.month);
+        assert_eq!(2000, system_time.year);
+    }
+
+    #[test]
+    fn system_time_from_unix_time_millis() {
+        let system_time = SystemTime::from_unix_time_millis(1654424430999);
+
+        assert_eq!(10, system_time.hours);
+        assert_eq!(20, system_time.minutes);
+        assert_eq!(30, system_time.seconds);
+        assert_eq!(5, system_time.day);
+        assert_eq!(6, system_time.month);
+        assert_eq!(2022, system_time.year);
+    }
+
+    #[test]
+    fn system_time_from_negative_unix_timestamp() {
+        let system_time = SystemTime::from_unix_timestamp(-86400);
+
+        assert_eq!(1, system_time.day);
+        assert_eq!(1, system_time.month);
+        assert_eq!(1970, system_time.year);
+    }
+
+    #[test]
+    fn system_time_from_dos_date_time() {
+        let system_time = SystemTime::from_dos_date_time(0x54C5, 0x528F);
+
+        assert_eq!(10, system_time.hours);
+        assert_eq!(20, system_time.minutes);
+        assert_eq!(30, system_time.seconds);
+        assert_eq!(5, system_time.day);
+        assert_eq!(6, system_time.month);
+        assert_eq!(2022, system_time.year);
+    }
+
+    #[test]
+    fn system_time_to_unix_time_millis() {
+        assert_eq!(0, SystemTime::from_unix_timestamp(0).to_unix_time_millis());
+        assert_eq!(1654424430000, SystemTime::from_unix_timestamp(1654424430).to_unix_time_millis());
+        assert_eq!(951868799000, SystemTime::from_unix_timestamp(951868799).to_unix_time_millis());
+        assert_eq!(253402300799000, SystemTime::from_unix_timestamp(253402300799).to_unix_time_millis());
+    }
 }
\ No newline at end of file
@@ -1,14 +1,48 @@
 use crate::date_time_converter::{DosDateTimeCalculator, SystemTime};
 
+const DOS_MINIMUM_YEAR: u16 = 1980;
+const DOS_MAXIMUM_YEAR: u16 = 2107;
+const DOS_MINIMUM_DATE: u16 = 0x0021;
+const DOS_MAXIMUM_DATE: u16 = 0xFF9F;
+const DOS_MAXIMUM_TIME: u16 = 0xBF7D;
+
 pub struct DosDateTimeCalculatorAdapter {
     pub(crate) date_time: SystemTime,
+    pub(crate) unix_time_millis: i64,
+    pub(crate) to_date_time: fn(i64) -> SystemTime,
 }
 
 impl DosDateTimeCalculator for DosDateTimeCalculatorAdapter {
     fn get_current_dos_time(&self) -> u16 {
-        let hours = self.date_time.hours;
-        let minutes = self.date_time.minutes;
-        let seconds = self.date_time.seconds;
+        self.get_dos_time(&self.date_time)
+    }
+
+    fn get_current_dos_date(&self) -> u16 {
+        self.get_dos_date(&self.date_time)
+    }
+
+    fn get_current_unix_time_millis(&self) -> i64 {
+        self.unix_time_millis
+    }
+
+    //Uses the same conversion as the current time, so that given and current times share a time zone
+    fn get_date_time(&self, unix_time_millis: i64) -> SystemTime {
+        (self.to_date_time)(unix_time_millis)
+    }
+
+    //Times outside of the years representable by MS-DOS are clamped to the nearest representable time
+    fn get_dos_time(&self, date_time: &SystemTime) -> u16 {
+        if date_time.year < DOS_MINIMUM_YEAR {
+            return 0;
+        }
+
+        if date_time.year > DOS_MAXIMUM_YEAR {
+            return DOS_MAXIMUM_TIME;
+        }
+
+        let hours = date_time.hours;
+        let minutes = date_time.minutes;
+        let seconds = date_time.seconds;
 
         let mut dos_time: u16 = 0;
 
@@ -19,10 +53,18 @@ impl DosDateTimeCalculator for DosDateTimeCalculatorAdapter {
         dos_time
     }
 
-    fn get_current_dos_date(&self) -> u16 {
-        let day = self.date_time.day;
-        let month = self.date_time.month;
-        let years_since_1980 = self.date_time.year - 1980;
+    fn get_dos_date(&self, date_time: &SystemTime) -> u16 {
+        if date_time.year < DOS_MINIMUM_YEAR {
+            return DOS_MINIMUM_DATE;
+        }
+
+        if date_time.year > DOS_MAXIMUM_YEAR {
+            return DOS_MAXIMUM_DATE;
+        }
+
+        let day = date_time.day;
+        let month = date_time.month;
+        let years_since_1980 = date_time.year - 1980;
 
         let mut dos_date: u16 = 0;
 
@@ -52,7 +94,9 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_time = dos_date_time_retriever.get_current_dos_time();
@@ -72,7 +116,9 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_time = dos_date_time_retriever.get_current_dos_time();
@@ -92,7 +138,9 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_time = dos_date_time_retriever.get_current_dos_time();
@@ -112,7 +160,9 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_date = dos_date_time_retriever.get_current_dos_date();
@@ -132,7 +182,9 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_date = dos_date_time_retriever.get_current_dos_date();
@@ -152,11 +204,162 @@ mod tests {
         };
 
         let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
-            date_time: fake_system_time
+            date_time: fake_system_time,
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
         };
 
         let dos_date = dos_date_time_retriever.get_current_dos_date();
 
         assert_eq!(0b1110011000111100, dos_date);
     }
+
+    #[test]
+    fn dos_time_of_given_date_time() {
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: SystemTime {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day: 0,
+                month: 0,
+                year: 1980,
+            },
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
+        };
+
+        let given_date_time = SystemTime {
+            seconds: 33,
+            minutes: 11,
+            hours: 6,
+            day: 10,
+            month: 7,
+            year: 1995,
+        };
+
+        let dos_time = dos_date_time_retriever.get_dos_time(&given_date_time);
+
+        assert_eq!(0b0011000101110000, dos_time);
+    }
+
+    #[test]
+    fn dos_date_of_given_date_time() {
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: SystemTime {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day: 0,
+                month: 0,
+                year: 1980,
+            },
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
+        };
+
+        let given_date_time = SystemTime {
+            seconds: 33,
+            minutes: 11,
+            hours: 6,
+            day: 10,
+            month: 7,
+            year: 1995,
+        };
+
+        let dos_date = dos_date_time_retriever.get_dos_date(&given_date_time);
+
+        assert_eq!(0b0001111011101010, dos_date);
+    }
+
+    #[test]
+    fn dos_date_time_before_1980() {
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: SystemTime {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day: 0,
+                month: 0,
+                year: 1980,
+            },
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
+        };
+
+        let given_date_time = SystemTime {
+            seconds: 33,
+            minutes: 11,
+            hours: 6,
+            day: 10,
+            month: 7,
+            year: 1975,
+        };
+
+        assert_eq!(0, dos_date_time_retriever.get_dos_time(&given_date_time));
+        assert_eq!(0b0000000000100001, dos_date_time_retriever.get_dos_date(&given_date_time));
+    }
+
+    #[test]
+    fn dos_date_time_after_2107() {
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: SystemTime {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day: 0,
+                month: 0,
+                year: 1980,
+            },
+            unix_time_millis: 0,
+            to_date_time: SystemTime::from_unix_time_millis,
+        };
+
+        let given_date_time = SystemTime {
+            seconds: 33,
+            minutes: 11,
+            hours: 6,
+            day: 10,
+            month: 7,
+            year: 2108,
+        };
+
+        assert_eq!(0b1011111101111101, dos_date_time_retriever.get_dos_time(&given_date_time));
+        assert_eq!(0b1111111110011111, dos_date_time_retriever.get_dos_date(&given_date_time));
+    }
+
+    #[test]
+    fn current_unix_time_millis() {
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: SystemTime {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day: 0,
+                month: 0,
+                year: 1980,
+            },
+            unix_time_millis: 1654424430000,
+            to_date_time: SystemTime::from_unix_time_millis,
+        };
+
+        assert_eq!(1654424430000, dos_date_time_retriever.get_current_unix_time_millis());
+    }
+
+    #[test]
+    fn date_time_uses_the_same_conversion_as_the_current_time() {
+        let to_date_time: fn(i64) -> SystemTime = |unix_time_millis| SystemTime::from_unix_time_millis(unix_time_millis + 9 * 3600 * 1000);
+
+        let dos_date_time_retriever = DosDateTimeCalculatorAdapter {
+            date_time: to_date_time(1654424430000),
+            unix_time_millis: 1654424430000,
+            to_date_time,
+        };
+
+        let date_time = dos_date_time_retriever.get_date_time(1654424430000);
+
+        assert_eq!(dos_date_time_retriever.get_current_dos_time(), dos_date_time_retriever.get_dos_time(&date_time));
+        assert_eq!(dos_date_time_retriever.get_current_dos_date(), dos_date_time_retriever.get_dos_date(&date_time));
+        assert_eq!(19, date_time.hours);
+    }
 }
\ No newline at end of file
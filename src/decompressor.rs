@@ -0,0 +1,5 @@
+pub mod inflate_decompressor_adapter;
+
+pub trait Decompressor {
+    fn decompress(&self, data: &[u8], uncompressed_size: usize) -> Option<Vec<u8>>;
+}
\ No newline at end of file
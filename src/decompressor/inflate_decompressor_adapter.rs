@@ -0,0 +1,45 @@
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+
+use crate::decompressor::Decompressor;
+
+pub struct InflateDecompressorAdapter {}
+
+impl Decompressor for InflateDecompressorAdapter {
+    //The size from the archive bounds the output, so a corrupted stream cannot inflate without limit
+    fn decompress(&self, data: &[u8], uncompressed_size: usize) -> Option<Vec<u8>> {
+        decompress_to_vec_with_limit(data, uncompressed_size).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miniz_oxide::deflate::compress_to_vec;
+
+    use super::*;
+
+    #[test]
+    fn compressed_data_inflates_to_original() {
+        let input_bytes = "Capoo is Hungry. Capoo is Hungry. Capoo is Hungry.".as_bytes();
+        let compressed_bytes = compress_to_vec(input_bytes, 6);
+
+        let decompressor = InflateDecompressorAdapter {};
+
+        assert_eq!(Some(input_bytes.to_vec()), decompressor.decompress(&compressed_bytes, input_bytes.len()));
+    }
+
+    #[test]
+    fn data_larger_than_uncompressed_size() {
+        let compressed_bytes = compress_to_vec(&[0x42; 1000], 6);
+
+        let decompressor = InflateDecompressorAdapter {};
+
+        assert_eq!(None, decompressor.decompress(&compressed_bytes, 999));
+    }
+
+    #[test]
+    fn invalid_data() {
+        let decompressor = InflateDecompressorAdapter {};
+
+        assert_eq!(None, decompressor.decompress(&[0xFF, 0xFF, 0xFF], 10));
+    }
+}
\ No newline at end of file
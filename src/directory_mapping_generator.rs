@@ -0,0 +1,55 @@
+use js_sys::{Map, Reflect, Uint8Array};
+use wasm_bindgen::JsValue;
+
+use crate::zip_error::ZipError;
+use crate::zip_file::FileDescriptor;
+use crate::zip_options_generator::{
+    get_central_directory_extra_fields, get_comment, get_compression_level, get_compression_method, get_created,
+    get_entry_type, get_gid, get_last_accessed, get_last_modified, get_local_extra_fields, get_mime_type, get_uid,
+    get_unix_mode,
+};
+
+pub fn generate_directory_mapping(directory_mapping: Map) -> Result<Vec<(String, FileDescriptor)>, ZipError> {
+    let mut file_name_to_descriptors = Vec::new();
+
+    for file_name in directory_mapping.keys() {
+        let file_name = file_name.unwrap();
+        let file_descriptor: FileDescriptor = get_file_descriptor(&directory_mapping, &file_name)?;
+        let file_name_string = file_name.as_string().unwrap();
+
+        file_name_to_descriptors.push((
+            file_name_string,
+            file_descriptor,
+        ));
+    }
+
+    Ok(file_name_to_descriptors)
+}
+
+fn get_file_descriptor(directory_mapping: &Map, file_name: &JsValue) -> Result<FileDescriptor, ZipError> {
+    let file_js_value = directory_mapping.get(file_name);
+    let file_name = file_name.as_string().unwrap_or_default();
+
+    Ok(FileDescriptor {
+        body: get_file_contents(&file_js_value),
+        compression_method: get_compression_method(&file_js_value)?,
+        compression_level: get_compression_level(&file_js_value),
+        mime_type: get_mime_type(&file_js_value),
+        entry_type: get_entry_type(&file_js_value),
+        last_modified: get_last_modified(&file_js_value),
+        last_accessed: get_last_accessed(&file_js_value),
+        created: get_created(&file_js_value),
+        unix_mode: get_unix_mode(&file_js_value),
+        uid: get_uid(&file_js_value),
+        gid: get_gid(&file_js_value),
+        local_extra_fields: get_local_extra_fields(&file_js_value, &file_name)?,
+        central_directory_extra_fields: get_central_directory_extra_fields(&file_js_value, &file_name)?,
+        comment: get_comment(&file_js_value),
+    })
+}
+
+fn get_file_contents(file_js_value: &JsValue) -> Vec<u8> {
+    let file_data = Reflect::get(file_js_value, &JsValue::from("data")).unwrap();
+
+    Uint8Array::new(&file_data).to_vec()
+}
\ No newline at end of file
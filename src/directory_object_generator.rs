@@ -0,0 +1,84 @@
+use js_sys::{Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::zip_archive::ZipArchive;
+use crate::zip_error::ZipError;
+
+const UNSAFE_PATH_SEGMENTS: [&str; 3] = [".", "..", "__proto__"];
+
+//Folders are nested objects and files are Uint8Arrays, which is the same shape that generate_zip_binary accepts
+pub fn generate_directory_object(zip_archive: &ZipArchive) -> Result<Object, ZipError> {
+    let directory_object = create_folder_object();
+
+    for zip_archive_entry in zip_archive.get_entries() {
+        let conflicting_entry = || ZipError::ConflictingEntryName { file_name: zip_archive_entry.file_name.clone() };
+
+        let mut path_segments: Vec<&str> = zip_archive_entry.file_name
+            .split('/')
+            .filter(|path_segment| !path_segment.is_empty())
+            .collect();
+
+        if path_segments.iter().any(|path_segment| UNSAFE_PATH_SEGMENTS.contains(path_segment)) {
+            return Err(ZipError::UnsafeEntryName { file_name: zip_archive_entry.file_name.clone() });
+        }
+
+        let file_name = match zip_archive_entry.is_directory() {
+            true => None,
+            false => path_segments.pop(),
+        };
+
+        let mut parent_object = directory_object.clone();
+
+        for path_segment in path_segments {
+            parent_object = get_sub_directory_object(&parent_object, path_segment).ok_or_else(conflicting_entry)?;
+        }
+
+        if let Some(file_name) = file_name {
+            if is_folder_object(&get_property(&parent_object, file_name)) {
+                return Err(conflicting_entry());
+            }
+
+            let file_contents = zip_archive.extract_entry(zip_archive_entry)?;
+
+            set_property(&parent_object, file_name, &Uint8Array::from(file_contents.as_slice()));
+        }
+    }
+
+    Ok(directory_object)
+}
+
+//Folders may be implied by the paths of their files without an entry of their own, so they are created on first use,
+//and a file already holding the name cannot become a folder
+fn get_sub_directory_object(parent_object: &Object, folder_name: &str) -> Option<Object> {
+    let existing_object = get_property(parent_object, folder_name);
+
+    if is_folder_object(&existing_object) {
+        return Some(existing_object.unchecked_into());
+    }
+
+    if !existing_object.is_undefined() {
+        return None;
+    }
+
+    let sub_directory_object = create_folder_object();
+    set_property(parent_object, folder_name, &sub_directory_object);
+
+    Some(sub_directory_object)
+}
+
+//Without a prototype, entry names such as constructor are plain keys and cannot reach Object.prototype
+fn create_folder_object() -> Object {
+    Object::create(JsValue::NULL.unchecked_ref())
+}
+
+fn is_folder_object(value: &JsValue) -> bool {
+    value.is_object() && !value.is_instance_of::<Uint8Array>()
+}
+
+fn get_property(object: &Object, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from(key)).unwrap()
+}
+
+fn set_property(object: &Object, key: &str, value: &JsValue) {
+    Reflect::set(object, &JsValue::from(key), value).unwrap();
+}
\ No newline at end of file
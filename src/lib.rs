@@ -1,19 +1,37 @@
+use std::collections::HashSet;
+
 use crc::{Crc, CRC_32_ISO_HDLC};
-use js_sys::{Map, Object};
+use js_sys::{Array, Map, Object, Uint8Array};
 use wasm_bindgen::prelude::*;
 
 use crc_calculator::crc_calculator_adapter::CrcCalculatorAdapter;
 use crc_calculator::CrcCalculator;
 
+use crate::compressor::deflate_compressor_adapter::DeflateCompressorAdapter;
 use crate::date_time_converter::dos_date_time_calculator_adapter::DosDateTimeCalculatorAdapter;
-use crate::date_time_converter::SystemTime;
+use crate::date_time_converter::{get_current_unix_time_millis, SystemTime};
 use crate::zip_file::zip_blob_factory::ZipBlobFactoryAdapter;
 use crate::zip_file::ZipBlobFactory;
 
+mod compressor;
 mod crc_calculator;
+mod decompressor;
 mod zip_file;
 mod date_time_converter;
-mod directory_hash_map_generator;
+mod directory_mapping_generator;
+mod directory_object_generator;
+mod zip_error;
+mod zip_options_generator;
+mod zip_writer;
+mod zip_archive;
+mod zip_entry_list_generator;
+
+pub use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+pub use crate::zip_archive::ZipArchive;
+pub use crate::zip_error::ZipError;
+pub use crate::zip_file::extra_field::ExtraField;
+pub use crate::zip_file::{CompressionMethod, EntryType, FileDescriptor, ZipOptions};
+pub use crate::zip_writer::ZipWriter;
 
 #[wasm_bindgen(module = "/js/create_directory_mapping.js")]
 extern "C" {
@@ -22,36 +40,153 @@ extern "C" {
 
 #[wasm_bindgen(module = "/js/get_system_time.js")]
 extern "C" {
-    fn get_system_time() -> Box<[u16]>;
+    fn get_system_time(unix_time_millis: f64) -> Box<[u16]>;
+}
+
+#[wasm_bindgen]
+pub fn generate_zip_binary(zip_contents: Object) -> Result<Box<[u8]>, JsError> {
+    create_zip_binary(zip_contents, ZipOptions::default())
+}
+
+#[wasm_bindgen]
+pub fn generate_zip_binary_with_options(zip_contents: Object, options: Object) -> Result<Box<[u8]>, JsError> {
+    let zip_options = zip_options_generator::generate_zip_options(&options)?;
+
+    create_zip_binary(zip_contents, zip_options)
+}
+
+#[wasm_bindgen]
+pub fn list_zip_entries(zip_binary: Uint8Array) -> Result<Array, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
+
+    Ok(zip_entry_list_generator::generate_zip_entry_list(&zip_archive))
+}
+
+#[wasm_bindgen]
+pub fn extract_zip_binary(zip_binary: Uint8Array) -> Result<Object, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
+
+    Ok(directory_object_generator::generate_directory_object(&zip_archive)?)
+}
+
+#[wasm_bindgen]
+pub fn extract_zip_entry(zip_binary: Uint8Array, pattern: &str) -> Result<Uint8Array, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
+
+    //When several files match, the first one in the central directory is returned
+    let zip_archive_entry = zip_archive.find_entries(pattern)
+        .into_iter()
+        .find(|zip_archive_entry| !zip_archive_entry.is_directory())
+        .ok_or_else(|| ZipError::EntryNotFound { pattern: pattern.to_string() })?;
+
+    Ok(Uint8Array::from(zip_archive.extract_entry(zip_archive_entry)?.as_slice()))
+}
+
+#[wasm_bindgen]
+pub fn append_zip_binary(zip_binary: Uint8Array, zip_contents: Object) -> Result<Box<[u8]>, JsError> {
+    append_to_zip_binary(zip_binary, zip_contents, ZipOptions::default())
 }
 
 #[wasm_bindgen]
-pub fn generate_zip_binary(zip_contents: Object) -> Box<[u8]> {
-    let directory_mapping = create_directory_mapping(&zip_contents, String::from(""));
+pub fn append_zip_binary_with_options(zip_binary: Uint8Array, zip_contents: Object, options: Object) -> Result<Box<[u8]>, JsError> {
+    let zip_options = zip_options_generator::generate_zip_options(&options)?;
 
-    let directory_hash_map = directory_hash_map_generator::generate_directory_mapping(directory_mapping);
+    append_to_zip_binary(zip_binary, zip_contents, zip_options)
+}
+
+#[wasm_bindgen]
+pub fn delete_zip_entries(zip_binary: Uint8Array, pattern: &str) -> Result<Box<[u8]>, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
 
+    let deleted_file_names: HashSet<&str> = zip_archive.find_entries(pattern)
+        .into_iter()
+        .map(|zip_archive_entry| zip_archive_entry.file_name.as_str())
+        .collect();
+
+    rewrite_zip_binary(&zip_archive, &|zip_archive_entry| {
+        if deleted_file_names.contains(zip_archive_entry.file_name.as_str()) {
+            return None;
+        }
+
+        Some(zip_archive_entry.file_name.clone())
+    })
+}
+
+#[wasm_bindgen]
+pub fn rename_zip_entries(zip_binary: Uint8Array, file_name: &str, new_file_name: &str) -> Result<Box<[u8]>, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
+
+    let renamed_file_names = zip_archive.get_renamed_file_names(file_name, new_file_name)?;
+
+    rewrite_zip_binary(&zip_archive, &|zip_archive_entry| {
+        renamed_file_names.get(&zip_archive_entry.file_name).cloned().or_else(|| Some(zip_archive_entry.file_name.clone()))
+    })
+}
+
+fn create_zip_binary(zip_contents: Object, options: ZipOptions) -> Result<Box<[u8]>, JsError> {
+    let file_descriptors = get_file_descriptors(&zip_contents)?;
+
+    Ok(create_zip_blob_factory(options).create_zip_binary(file_descriptors)?)
+}
+
+fn append_to_zip_binary(zip_binary: Uint8Array, zip_contents: Object, options: ZipOptions) -> Result<Box<[u8]>, JsError> {
+    let zip_binary = zip_binary.to_vec();
+    let zip_archive = ZipArchive::new(&zip_binary)?;
+    let file_descriptors = get_file_descriptors(&zip_contents)?;
+
+    Ok(create_zip_blob_factory(options).append_zip_binary(&zip_archive, file_descriptors)?)
+}
+
+fn rewrite_zip_binary(zip_archive: &ZipArchive, get_new_file_name: &dyn Fn(&ZipArchiveEntry) -> Option<String>) -> Result<Box<[u8]>, JsError> {
+    Ok(create_zip_blob_factory(ZipOptions::default()).rewrite_zip_binary(zip_archive, get_new_file_name)?)
+}
+
+fn get_file_descriptors(zip_contents: &Object) -> Result<Vec<(String, FileDescriptor)>, ZipError> {
+    let directory_mapping = create_directory_mapping(zip_contents, String::from(""));
+
+    directory_mapping_generator::generate_directory_mapping(directory_mapping)
+}
+
+fn create_zip_blob_factory(options: ZipOptions) -> ZipBlobFactoryAdapter {
     let crc_calculator = Box::new(CrcCalculatorAdapter {
         crc: Crc::<u32>::new(&CRC_32_ISO_HDLC)
     });
 
-    let browser_time = get_system_time();
+    let unix_time_millis = options.timestamp.unwrap_or_else(get_current_unix_time_millis);
+
+    //Fixed timestamps are stamped in UTC to stay reproducible across time zones, and all other times in the browser's time zone
+    let to_date_time: fn(i64) -> SystemTime = if options.timestamp.is_some() { SystemTime::from_unix_time_millis } else { get_local_date_time };
 
     let date_time_retriever = Box::new(DosDateTimeCalculatorAdapter {
-        date_time: SystemTime {
-            hours: browser_time[0],
-            minutes: browser_time[1],
-            seconds: browser_time[2],
-            day: browser_time[3],
-            month: browser_time[4],
-            year: browser_time[5],
-        }
+        date_time: to_date_time(unix_time_millis),
+        unix_time_millis,
+        to_date_time,
     });
 
-    let zip_blob_factory = ZipBlobFactoryAdapter {
+    let compressor = Box::new(DeflateCompressorAdapter {});
+
+    ZipBlobFactoryAdapter {
         crc_calculator,
         date_time_retriever,
-    };
-
-    zip_blob_factory.create_zip_binary(directory_hash_map)
+        compressor,
+        options,
+    }
 }
+
+fn get_local_date_time(unix_time_millis: i64) -> SystemTime {
+    let browser_time = get_system_time(unix_time_millis as f64);
+
+    SystemTime {
+        hours: browser_time[0],
+        minutes: browser_time[1],
+        seconds: browser_time[2],
+        day: browser_time[3],
+        month: browser_time[4],
+        year: browser_time[5],
+    }
+}
\ No newline at end of file
@@ -0,0 +1,516 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use crc::{Crc, CRC_32_ISO_HDLC};
+
+use crate::crc_calculator::crc_calculator_adapter::CrcCalculatorAdapter;
+use crate::decompressor::inflate_decompressor_adapter::InflateDecompressorAdapter;
+use crate::zip_archive::glob_pattern::matches_glob_pattern;
+use crate::zip_archive::raw_zip_archive_entry::RawZipArchiveEntry;
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::zip_entry_extractor::ZipEntryExtractorAdapter;
+use crate::zip_error::ZipError;
+use crate::zip_file::zip_blob_factory::CentralDirectorySummary;
+use crate::zip_file::{
+    COMMENT_LENGTH_LIMIT, END_OF_CENTRAL_DIRECTORY_SIGNATURE, END_OF_CENTRAL_DIRECTORY_SIZE, LOCAL_FILE_HEADER_SIGNATURE,
+    ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIGNATURE, ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIZE, ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE,
+};
+
+mod glob_pattern;
+mod raw_zip_archive_entry;
+pub mod zip_archive_entry;
+pub mod zip_entry_extractor;
+
+const CP437_UPPER_HALF: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{A0}";
+
+pub struct ZipArchive<'a> {
+    data: &'a [u8],
+    central_directory_summary: CentralDirectorySummary,
+    entries: Vec<ZipArchiveEntry>,
+    raw_comment: &'a [u8],
+    comment: String,
+}
+
+pub trait ZipEntryExtractor {
+    fn extract_entry(&self, data: &[u8], zip_archive_entry: &ZipArchiveEntry) -> Result<Vec<u8>, ZipError>;
+}
+
+impl<'a> ZipArchive<'a> {
+    pub fn new(data: &'a [u8]) -> Result<ZipArchive<'a>, ZipError> {
+        let end_of_central_directory_offset = find_end_of_central_directory(data)?;
+        let central_directory_summary = read_central_directory_summary(data, end_of_central_directory_offset)?;
+
+        let comment_length = read_u16(data, end_of_central_directory_offset + 20).unwrap_or(0) as usize;
+        let comment = read_bytes(data, end_of_central_directory_offset + END_OF_CENTRAL_DIRECTORY_SIZE, comment_length).unwrap_or(&[]);
+
+        Ok(ZipArchive {
+            data,
+            entries: read_central_directory(data, &central_directory_summary)?,
+            central_directory_summary,
+            raw_comment: comment,
+            comment: decode_comment(comment),
+        })
+    }
+
+    pub fn get_entries(&self) -> &[ZipArchiveEntry] {
+        &self.entries
+    }
+
+    pub fn get_comment(&self) -> &str {
+        &self.comment
+    }
+
+    pub(crate) fn get_data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    //The comment as stored in the archive, so that it can be written back without being re-encoded
+    pub(crate) fn get_raw_comment(&self) -> &'a [u8] {
+        self.raw_comment
+    }
+
+    pub(crate) fn get_central_directory_summary(&self) -> &CentralDirectorySummary {
+        &self.central_directory_summary
+    }
+
+    //Each local record runs until the next local file header or the central directory, so that it includes any data
+    //descriptor without having to parse it
+    pub(crate) fn get_raw_entries(&self) -> Result<Vec<RawZipArchiveEntry<'_>>, ZipError> {
+        let central_directory_start = self.central_directory_summary.start_offset;
+
+        let mut local_file_header_offsets: Vec<u64> = self.entries.iter().map(|zip_archive_entry| zip_archive_entry.entry_offset).collect();
+        local_file_header_offsets.sort_unstable();
+
+        let mut raw_entries: Vec<RawZipArchiveEntry> = Vec::new();
+        let mut header_offset = usize::try_from(central_directory_start).map_err(|_| ZipError::InvalidCentralDirectory)?;
+
+        for zip_archive_entry in &self.entries {
+            let (_, header_size) = ZipArchiveEntry::from_central_directory_header(self.data, header_offset)?;
+            let central_directory_header = read_bytes(self.data, header_offset, header_size).ok_or(ZipError::InvalidCentralDirectory)?;
+
+            let local_file_record_end = local_file_header_offsets.iter()
+                .copied()
+                .find(|&offset| offset > zip_archive_entry.entry_offset)
+                .unwrap_or(central_directory_start)
+                .min(central_directory_start);
+
+            let local_file_record = usize::try_from(zip_archive_entry.entry_offset).ok()
+                .zip(usize::try_from(local_file_record_end).ok())
+                .and_then(|(start, end)| self.data.get(start..end))
+                .filter(|local_file_record| read_u32(local_file_record, 0) == Some(LOCAL_FILE_HEADER_SIGNATURE))
+                .ok_or_else(|| ZipError::InvalidLocalFileHeader { file_name: zip_archive_entry.file_name.clone() })?;
+
+            raw_entries.push(RawZipArchiveEntry {
+                zip_archive_entry,
+                local_file_record,
+                central_directory_header,
+            });
+
+            header_offset += header_size;
+        }
+
+        Ok(raw_entries)
+    }
+
+    //Only the central directory is searched, so the data of the other entries is never read. Entries named exactly like
+    //the pattern are returned on their own, so that names containing ? or * can still be selected literally
+    pub fn find_entries(&self, pattern: &str) -> Vec<&ZipArchiveEntry> {
+        let exact_entries: Vec<&ZipArchiveEntry> = self.entries.iter()
+            .filter(|zip_archive_entry| zip_archive_entry.file_name == pattern)
+            .collect();
+
+        if !exact_entries.is_empty() {
+            return exact_entries;
+        }
+
+        self.entries.iter()
+            .filter(|zip_archive_entry| matches_glob_pattern(pattern, &zip_archive_entry.file_name))
+            .collect()
+    }
+
+    //All new names are worked out before anything is written. Entries that are not renamed keep their names and folders,
+    //so the new name must not be one of them or lie inside one of their files
+    pub fn get_renamed_file_names(&self, file_name: &str, new_file_name: &str) -> Result<HashMap<String, String>, ZipError> {
+        let renamed_file_names: HashMap<String, String> = self.entries.iter()
+            .filter_map(|zip_archive_entry| {
+                let renamed_file_name = zip_archive_entry.get_renamed_file_name(file_name, new_file_name)?;
+                Some((zip_archive_entry.file_name.clone(), renamed_file_name))
+            })
+            .collect();
+
+        if renamed_file_names.is_empty() {
+            return Err(ZipError::EntryNotFound { pattern: file_name.to_string() });
+        }
+
+        let new_folder_name = new_file_name.trim_end_matches('/');
+
+        if new_folder_name.is_empty() || new_folder_name.starts_with('/') {
+            return Err(ZipError::InvalidEntryName { file_name: new_file_name.to_string() });
+        }
+
+        let mut taken_names: HashSet<&str> = HashSet::new();
+        let mut taken_file_names: HashSet<&str> = HashSet::new();
+
+        for zip_archive_entry in self.entries.iter().filter(|zip_archive_entry| !renamed_file_names.contains_key(&zip_archive_entry.file_name)) {
+            let name = zip_archive_entry.file_name.trim_end_matches('/');
+
+            taken_names.extend(get_parent_folder_names(name));
+            taken_names.insert(name);
+
+            if !zip_archive_entry.is_directory() {
+                taken_file_names.insert(name);
+            }
+        }
+
+        if taken_names.contains(new_folder_name) || get_parent_folder_names(new_folder_name).any(|parent_folder_name| taken_file_names.contains(parent_folder_name)) {
+            return Err(ZipError::ConflictingEntryName { file_name: new_file_name.to_string() });
+        }
+
+        Ok(renamed_file_names)
+    }
+
+    //Decompresses the data of an entry, after checking it against the CRC-32 and size in the central directory
+    pub fn extract_entry(&self, zip_archive_entry: &ZipArchiveEntry) -> Result<Vec<u8>, ZipError> {
+        let zip_entry_extractor = ZipEntryExtractorAdapter {
+            crc_calculator: Box::new(CrcCalculatorAdapter {
+                crc: Crc::<u32>::new(&CRC_32_ISO_HDLC)
+            }),
+            decompressor: Box::new(InflateDecompressorAdapter {}),
+        };
+
+        zip_entry_extractor.extract_entry(self.data, zip_archive_entry)
+    }
+}
+
+//The record is searched for backwards, as it can only be followed by a comment of up to 65535 bytes
+fn find_end_of_central_directory(data: &[u8]) -> Result<usize, ZipError> {
+    let last_offset = data.len().checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE).ok_or(ZipError::EndOfCentralDirectoryNotFound)?;
+    let first_offset = last_offset.saturating_sub(COMMENT_LENGTH_LIMIT);
+
+    (first_offset..=last_offset).rev()
+        .find(|&offset| {
+            let comment_length = read_u16(data, offset + 20).unwrap_or(0) as usize;
+
+            read_u32(data, offset) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+                && offset + END_OF_CENTRAL_DIRECTORY_SIZE + comment_length <= data.len()
+        })
+        .ok_or(ZipError::EndOfCentralDirectoryNotFound)
+}
+
+fn read_central_directory_summary(data: &[u8], end_of_central_directory_offset: usize) -> Result<CentralDirectorySummary, ZipError> {
+    let field = |field_offset: usize| read_u16(data, end_of_central_directory_offset + field_offset).unwrap_or(0);
+    let long_field = |field_offset: usize| read_u32(data, end_of_central_directory_offset + field_offset).unwrap_or(0);
+
+    if field(4) != 0 || field(6) != 0 {
+        return Err(ZipError::MultiDiskArchive);
+    }
+
+    if let Some(locator_offset) = end_of_central_directory_offset.checked_sub(ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIZE) {
+        if read_u32(data, locator_offset) == Some(ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIGNATURE) {
+            return read_zip64_central_directory_summary(data, locator_offset);
+        }
+    }
+
+    Ok(CentralDirectorySummary {
+        number_of_records: u64::from(field(10)),
+        size: u64::from(long_field(12)),
+        start_offset: u64::from(long_field(16)),
+    })
+}
+
+fn read_zip64_central_directory_summary(data: &[u8], locator_offset: usize) -> Result<CentralDirectorySummary, ZipError> {
+    let zip64_end_of_central_directory_offset = read_u64(data, locator_offset + 8)
+        .and_then(|offset| usize::try_from(offset).ok())
+        .ok_or(ZipError::InvalidCentralDirectory)?;
+
+    if read_u32(data, zip64_end_of_central_directory_offset) != Some(ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE) {
+        return Err(ZipError::InvalidCentralDirectory);
+    }
+
+    let long_field = |field_offset: usize| {
+        read_u64(data, zip64_end_of_central_directory_offset + field_offset).ok_or(ZipError::InvalidCentralDirectory)
+    };
+
+    Ok(CentralDirectorySummary {
+        number_of_records: long_field(32)?,
+        size: long_field(40)?,
+        start_offset: long_field(48)?,
+    })
+}
+
+fn read_central_directory(data: &[u8], central_directory_summary: &CentralDirectorySummary) -> Result<Vec<ZipArchiveEntry>, ZipError> {
+    let mut entries: Vec<ZipArchiveEntry> = Vec::new();
+    let mut header_offset = usize::try_from(central_directory_summary.start_offset).map_err(|_| ZipError::InvalidCentralDirectory)?;
+
+    for _ in 0..central_directory_summary.number_of_records {
+        let (entry, header_size) = ZipArchiveEntry::from_central_directory_header(data, header_offset)?;
+
+        entries.push(entry);
+        header_offset += header_size;
+    }
+
+    Ok(entries)
+}
+
+fn get_parent_folder_names(file_name: &str) -> impl Iterator<Item = &str> {
+    file_name.match_indices('/').map(move |(separator_index, _)| &file_name[..separator_index])
+}
+
+//Archive comments have no encoding flag, so UTF-8 is assumed whenever the comment is valid UTF-8
+fn decode_comment(comment: &[u8]) -> String {
+    match std::str::from_utf8(comment) {
+        Ok(comment) => comment.to_string(),
+        Err(_) => decode_cp437(comment),
+    }
+}
+
+fn decode_cp437(text: &[u8]) -> String {
+    text.iter()
+        .map(|&character| match character {
+            0x00..=0x7F => character as char,
+            _ => CP437_UPPER_HALF.chars().nth(character as usize - 0x80).unwrap_or(char::REPLACEMENT_CHARACTER),
+        })
+        .collect()
+}
+
+fn read_bytes(data: &[u8], offset: usize, length: usize) -> Option<&[u8]> {
+    data.get(offset..offset.checked_add(length)?)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = read_bytes(data, offset, 2)?;
+
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = read_bytes(data, offset, 4)?;
+
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = read_bytes(data, offset, 8)?;
+
+    let mut value_bytes = [0; 8];
+    value_bytes.copy_from_slice(bytes);
+
+    Some(u64::from_le_bytes(value_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::zip_file::{EntryType, FileDescriptor, ZipOptions};
+    use crate::zip_writer::ZipWriter;
+
+    const TIMESTAMP: i64 = 1654424430000;
+
+    fn create_zip_binary(options: ZipOptions) -> Vec<u8> {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..options });
+
+        zip_writer.start_file("Capoo.txt", FileDescriptor { comment: Some(String::from("Hungry")), ..FileDescriptor::default() }).unwrap();
+        zip_writer.write_all(b"Capoo is Hungry. Capoo is Hungry.").unwrap();
+        zip_writer.start_file("猫/BugCat.bin", FileDescriptor { body: vec![1, 2, 3], ..FileDescriptor::default() }).unwrap();
+        zip_writer.start_file("Empty", FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }).unwrap();
+
+        zip_writer.finish().unwrap()
+    }
+
+    #[test]
+    fn zip_archive_should_list_written_entries() {
+        let zip_binary = create_zip_binary(ZipOptions { comment: String::from("Capoo archive"), ..ZipOptions::default() });
+
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+        let entries = zip_archive.get_entries();
+
+        assert_eq!("Capoo archive", zip_archive.get_comment());
+        assert_eq!(4, entries.len());
+
+        assert_eq!("Capoo.txt", entries[0].file_name);
+        assert_eq!(0x0008, entries[0].compression_method);
+        assert_eq!(33, entries[0].uncompressed_size);
+        assert_eq!(TIMESTAMP, entries[0].get_last_modified());
+        assert_eq!("Hungry", entries[0].comment);
+        assert_eq!(0, entries[0].entry_offset);
+
+        assert_eq!("猫/", entries[1].file_name);
+        assert!(entries[1].is_directory());
+
+        assert_eq!("猫/BugCat.bin", entries[2].file_name);
+        assert_eq!(3, entries[2].compressed_size);
+
+        assert_eq!("Empty/", entries[3].file_name);
+        assert!(entries[3].is_directory());
+    }
+
+    #[test]
+    fn zip_archive_should_read_zip64_records() {
+        let zip_binary = create_zip_binary(ZipOptions { force_zip64: true, ..ZipOptions::default() });
+
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+        let entries = zip_archive.get_entries();
+
+        assert_eq!(4, entries.len());
+        assert_eq!(33, entries[0].uncompressed_size);
+        assert_eq!(3, entries[2].uncompressed_size);
+        assert_eq!(3, entries[2].compressed_size);
+        assert!(entries[2].entry_offset > 0);
+    }
+
+    #[test]
+    fn zip_archive_should_read_empty_archive() {
+        let zip_binary = ZipWriter::new(Vec::new()).finish().unwrap();
+
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+
+        assert!(zip_archive.get_entries().is_empty());
+    }
+
+    #[test]
+    fn zip_archive_should_reject_data_without_end_of_central_directory() {
+        let zip_error = ZipArchive::new(b"Capoo is Hungry. Capoo is Hungry.").err();
+
+        assert_eq!(Some(ZipError::EndOfCentralDirectoryNotFound), zip_error);
+    }
+
+    #[test]
+    fn zip_archive_should_reject_truncated_central_directory() {
+        let zip_binary = create_zip_binary(ZipOptions::default());
+        let central_directory_start = ZipArchive::new(&zip_binary).unwrap().get_entries()[3].entry_offset as usize + 36;
+        let end_of_central_directory = &zip_binary[zip_binary.len() - END_OF_CENTRAL_DIRECTORY_SIZE..];
+
+        let truncated_zip_binary = [&zip_binary[..central_directory_start], end_of_central_directory].concat();
+
+        let zip_error = ZipArchive::new(&truncated_zip_binary).err();
+
+        assert_eq!(Some(ZipError::InvalidCentralDirectory), zip_error);
+    }
+
+    #[test]
+    fn zip_archive_should_reject_multi_disk_archives() {
+        let mut zip_binary = create_zip_binary(ZipOptions::default());
+        let number_of_disk_offset = zip_binary.len() - END_OF_CENTRAL_DIRECTORY_SIZE + 4;
+        zip_binary[number_of_disk_offset] = 0x01;
+
+        let zip_error = ZipArchive::new(&zip_binary).err();
+
+        assert_eq!(Some(ZipError::MultiDiskArchive), zip_error);
+    }
+
+    #[test]
+    fn zip_archive_should_extract_written_entries() {
+        for options in [ZipOptions::default(), ZipOptions { force_zip64: true, data_descriptor: true, ..ZipOptions::default() }] {
+            let zip_binary = create_zip_binary(options);
+
+            let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+            let entries = zip_archive.get_entries();
+
+            assert_eq!(Ok(b"Capoo is Hungry. Capoo is Hungry.".to_vec()), zip_archive.extract_entry(&entries[0]));
+            assert_eq!(Ok(vec![]), zip_archive.extract_entry(&entries[1]));
+            assert_eq!(Ok(vec![1, 2, 3]), zip_archive.extract_entry(&entries[2]));
+        }
+    }
+
+    #[test]
+    fn zip_archive_should_find_entries_matching_pattern() {
+        let zip_binary = create_zip_binary(ZipOptions::default());
+
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+
+        let file_names = |pattern: &str| -> Vec<String> {
+            zip_archive.find_entries(pattern).iter().map(|zip_archive_entry| zip_archive_entry.file_name.clone()).collect()
+        };
+
+        assert_eq!(vec!["猫/BugCat.bin"], file_names("猫/BugCat.bin"));
+        assert_eq!(vec!["Capoo.txt", "猫/BugCat.bin"], file_names("**/*.*"));
+        assert_eq!(vec!["猫/", "Empty/"], file_names("*/"));
+        assert!(file_names("BugCat.bin").is_empty());
+    }
+
+    #[test]
+    fn zip_archive_should_prefer_entries_named_like_the_pattern() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+
+        zip_writer.start_file("whatX.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("what?.txt", FileDescriptor::default()).unwrap();
+
+        let zip_binary = zip_writer.finish().unwrap();
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+
+        let file_names = |pattern: &str| -> Vec<String> {
+            zip_archive.find_entries(pattern).iter().map(|zip_archive_entry| zip_archive_entry.file_name.clone()).collect()
+        };
+
+        assert_eq!(vec!["what?.txt"], file_names("what?.txt"));
+        assert_eq!(vec!["whatX.txt", "what?.txt"], file_names("what*.txt"));
+    }
+
+    #[test]
+    fn zip_archive_should_rename_folder_with_its_entries() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+
+        zip_writer.start_file("Folder/", FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }).unwrap();
+        zip_writer.start_file("Folder/Capoo.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("a.txt", FileDescriptor::default()).unwrap();
+
+        let zip_binary = zip_writer.finish().unwrap();
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+
+        let renamed_file_names = zip_archive.get_renamed_file_names("Folder/", "Cats").unwrap();
+
+        assert_eq!(2, renamed_file_names.len());
+        assert_eq!("Cats/", renamed_file_names["Folder/"]);
+        assert_eq!("Cats/Capoo.txt", renamed_file_names["Folder/Capoo.txt"]);
+    }
+
+    #[test]
+    fn zip_archive_should_reject_conflicting_or_invalid_new_names() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+
+        zip_writer.start_file("Folder/", FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }).unwrap();
+        zip_writer.start_file("Folder/Capoo.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("Other/BugCat.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("a.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("b.txt", FileDescriptor::default()).unwrap();
+
+        let zip_binary = zip_writer.finish().unwrap();
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+
+        for (file_name, new_file_name) in [("a.txt", "b.txt"), ("a.txt", "Folder"), ("a.txt", "b.txt/c.txt"), ("Folder", "Other/"), ("Other", "Folder")] {
+            let zip_error = zip_archive.get_renamed_file_names(file_name, new_file_name).err();
+
+            assert_eq!(Some(ZipError::ConflictingEntryName { file_name: String::from(new_file_name) }), zip_error);
+        }
+
+        for new_file_name in ["", "/", "/c.txt"] {
+            let zip_error = zip_archive.get_renamed_file_names("a.txt", new_file_name).err();
+
+            assert_eq!(Some(ZipError::InvalidEntryName { file_name: String::from(new_file_name) }), zip_error);
+        }
+
+        let zip_error = zip_archive.get_renamed_file_names("Missing", "c.txt").err();
+
+        assert_eq!(Some(ZipError::EntryNotFound { pattern: String::from("Missing") }), zip_error);
+    }
+
+    #[test]
+    fn zip_archive_should_reject_corrupted_entries() {
+        let mut zip_binary = create_zip_binary(ZipOptions::default());
+        let bug_cat_data_offset = ZipArchive::new(&zip_binary).unwrap().get_entries()[2].entry_offset as usize + 30 + "猫/BugCat.bin".len();
+        zip_binary[bug_cat_data_offset] = 0x04;
+
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+        let zip_error = zip_archive.extract_entry(&zip_archive.get_entries()[2]).err();
+
+        assert_eq!(Some(ZipError::CorruptedEntry { file_name: String::from("猫/BugCat.bin") }), zip_error);
+    }
+
+    #[test]
+    fn decode_cp437_upper_half() {
+        assert_eq!("Ç ü é ■", decode_cp437(&[0x80, 0x20, 0x81, 0x20, 0x82, 0x20, 0xFE]));
+        assert_eq!(128, CP437_UPPER_HALF.chars().count());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+enum GlobToken {
+    Character(char),
+    AnyCharacter,
+    Star,
+    DoubleStar,
+    //A **/ matches zero or more whole folders
+    Folders,
+}
+
+//A * matches within a single path segment, while a ** also matches across folders, so that **/manifest.json matches
+//the file at any depth, including the root
+pub(crate) fn matches_glob_pattern(pattern: &str, file_name: &str) -> bool {
+    let file_name: Vec<char> = file_name.chars().collect();
+
+    //Each token is matched from every position reached by the tokens before it at once, so no split of the file name is
+    //tried more than once and the time stays proportional to the lengths of the pattern and the file name
+    let mut reached_positions = vec![false; file_name.len() + 1];
+    reached_positions[0] = true;
+
+    for glob_token in get_glob_tokens(pattern) {
+        reached_positions = get_next_reached_positions(&glob_token, &file_name, &reached_positions);
+
+        if !reached_positions.contains(&true) {
+            return false;
+        }
+    }
+
+    reached_positions[file_name.len()]
+}
+
+fn get_next_reached_positions(glob_token: &GlobToken, file_name: &[char], reached_positions: &[bool]) -> Vec<bool> {
+    let mut next_reached_positions = vec![false; reached_positions.len()];
+    let mut any_reached_before = false;
+
+    for position in 0..reached_positions.len() {
+        let previous_character = position.checked_sub(1).map(|previous_position| file_name[previous_position]);
+        let previous_reached = position > 0 && reached_positions[position - 1];
+        let previous_next_reached = position > 0 && next_reached_positions[position - 1];
+
+        next_reached_positions[position] = match glob_token {
+            GlobToken::Character(pattern_character) => previous_reached && previous_character == Some(*pattern_character),
+            GlobToken::AnyCharacter => previous_reached && previous_character != Some('/'),
+            GlobToken::Star => reached_positions[position] || (previous_next_reached && previous_character != Some('/')),
+            GlobToken::DoubleStar => reached_positions[position] || previous_next_reached,
+            GlobToken::Folders => reached_positions[position] || (any_reached_before && previous_character == Some('/')),
+        };
+
+        any_reached_before |= reached_positions[position];
+    }
+
+    next_reached_positions
+}
+
+fn get_glob_tokens(pattern: &str) -> Vec<GlobToken> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut glob_tokens: Vec<GlobToken> = Vec::new();
+    let mut index = 0;
+
+    while index < pattern.len() {
+        let (glob_token, token_length) = match pattern[index..] {
+            ['*', '*', '/', ..] => (GlobToken::Folders, 3),
+            ['*', '*', ..] => (GlobToken::DoubleStar, 2),
+            ['*', ..] => (GlobToken::Star, 1),
+            ['?', ..] => (GlobToken::AnyCharacter, 1),
+            _ => (GlobToken::Character(pattern[index]), 1),
+        };
+
+        glob_tokens.push(glob_token);
+        index += token_length;
+    }
+
+    glob_tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_pattern() {
+        assert!(matches_glob_pattern("manifest.json", "manifest.json"));
+        assert!(!matches_glob_pattern("manifest.json", "Folder/manifest.json"));
+        assert!(!matches_glob_pattern("manifest.json", "manifest.json5"));
+    }
+
+    #[test]
+    fn single_star_stays_within_folder() {
+        assert!(matches_glob_pattern("*.txt", "Capoo.txt"));
+        assert!(matches_glob_pattern("Folder/*.txt", "Folder/.txt"));
+        assert!(!matches_glob_pattern("*.txt", "Folder/Capoo.txt"));
+    }
+
+    #[test]
+    fn double_star_crosses_folders() {
+        assert!(matches_glob_pattern("**/manifest.json", "manifest.json"));
+        assert!(matches_glob_pattern("**/manifest.json", "a/b/manifest.json"));
+        assert!(matches_glob_pattern("Folder/**", "Folder/a/b.txt"));
+        assert!(!matches_glob_pattern("**/manifest.json", "a/b/manifest.json.bak"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches_glob_pattern("猫?.bin", "猫1.bin"));
+        assert!(!matches_glob_pattern("Folder?Capoo", "Folder/Capoo"));
+        assert!(!matches_glob_pattern("?", ""));
+    }
+
+    #[test]
+    fn stars_match_within_and_across_folders() {
+        assert!(matches_glob_pattern("**/*.txt", "a/b/c.txt"));
+        assert!(matches_glob_pattern("a/**/b", "a/b"));
+        assert!(matches_glob_pattern("a/**/b", "a/x/y/b"));
+        assert!(matches_glob_pattern("*a*b", "xaxab"));
+        assert!(matches_glob_pattern("**a/b", "xa/ba/b"));
+        assert!(!matches_glob_pattern("a/**/b", "a/xb"));
+        assert!(!matches_glob_pattern("*a*b", "xa/b"));
+    }
+
+    #[test]
+    fn repeated_stars_do_not_backtrack_exponentially() {
+        let file_name = "a".repeat(10000);
+
+        assert!(!matches_glob_pattern("*a*a*a*a*a*a*a*a*b", &file_name));
+        assert!(!matches_glob_pattern("**a**a**a**a**a**a**b", &file_name));
+    }
+}
\ No newline at end of file
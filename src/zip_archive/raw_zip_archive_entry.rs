@@ -0,0 +1,197 @@
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::{read_bytes, read_u16, read_u32};
+use crate::zip_error::ZipError;
+use crate::zip_file::extra_field::{parse_extra_fields, ExtraField, UNICODE_PATH_EXTRA_FIELD_ID, ZIP64_EXTRA_FIELD_ID};
+use crate::zip_file::{
+    CENTRAL_DIRECTORY_HEADER_SIZE, COMMENT_LENGTH_LIMIT, EXTRA_FIELD_LENGTH_LIMIT, LANGUAGE_ENCODING_FLAG, LOCAL_FILE_HEADER_SIZE,
+    ZIP64_FIELD_LIMIT, ZIP64_VERSION_NEEDED_TO_EXTRACT,
+};
+
+//The local record holds the local file header, the file data and any data descriptor, exactly as they are in the archive
+pub(crate) struct RawZipArchiveEntry<'a> {
+    pub(crate) zip_archive_entry: &'a ZipArchiveEntry,
+    pub(crate) local_file_record: &'a [u8],
+    pub(crate) central_directory_header: &'a [u8],
+}
+
+impl<'a> RawZipArchiveEntry<'a> {
+    //Only the header is rebuilt for a new name, so the file data is never decompressed
+    pub(crate) fn get_local_file_record(&self, file_name: &str, unicode_path_extra_field: Option<&ExtraField>) -> Result<Vec<u8>, ZipError> {
+        if file_name == self.zip_archive_entry.file_name {
+            return Ok(self.local_file_record.to_vec());
+        }
+
+        let invalid_local_file_header = || ZipError::InvalidLocalFileHeader { file_name: self.zip_archive_entry.file_name.clone() };
+
+        let header = read_bytes(self.local_file_record, 0, LOCAL_FILE_HEADER_SIZE).ok_or_else(invalid_local_file_header)?;
+        let file_name_length = read_u16(header, 26).unwrap_or(0) as usize;
+        let extra_field_length = read_u16(header, 28).unwrap_or(0) as usize;
+
+        let data_offset = LOCAL_FILE_HEADER_SIZE + file_name_length + extra_field_length;
+        let extra_fields = read_bytes(self.local_file_record, LOCAL_FILE_HEADER_SIZE + file_name_length, extra_field_length)
+            .ok_or_else(invalid_local_file_header)?;
+
+        let extra_fields = self.get_renamed_extra_fields(parse_extra_fields(extra_fields), unicode_path_extra_field)?;
+        let general_purpose_bit_flag = self.get_renamed_general_purpose_bit_flag(read_u16(header, 6).unwrap_or(0), file_name);
+
+        let mut local_file_record: Vec<u8> = header.to_vec();
+        local_file_record[6..8].copy_from_slice(&general_purpose_bit_flag.to_le_bytes());
+        local_file_record[26..28].copy_from_slice(&(file_name.len() as u16).to_le_bytes());
+        local_file_record[28..30].copy_from_slice(&(extra_fields.len() as u16).to_le_bytes());
+        local_file_record.extend_from_slice(file_name.as_bytes());
+        local_file_record.extend_from_slice(&extra_fields);
+        local_file_record.extend_from_slice(&self.local_file_record[data_offset..]);
+
+        Ok(local_file_record)
+    }
+
+    //The offset may need to move into the ZIP64 extra field, so the field is always written again from the new offset
+    pub(crate) fn get_central_directory_header(
+        &self,
+        file_name: &str,
+        unicode_path_extra_field: Option<&ExtraField>,
+        entry_offset: u64,
+    ) -> Result<Vec<u8>, ZipError> {
+        let header = self.central_directory_header;
+        let file_name_length = read_u16(header, 28).unwrap_or(0) as usize;
+        let extra_field_length = read_u16(header, 30).unwrap_or(0) as usize;
+        let comment_offset = CENTRAL_DIRECTORY_HEADER_SIZE + file_name_length + extra_field_length;
+        let renamed = file_name != self.zip_archive_entry.file_name;
+
+        let mut central_directory_header: Vec<u8> = header[..CENTRAL_DIRECTORY_HEADER_SIZE].to_vec();
+        let mut zip64_extra_field_data: Vec<u8> = Vec::new();
+
+        if read_u32(header, 24).map(u64::from) == Some(ZIP64_FIELD_LIMIT) {
+            zip64_extra_field_data.extend_from_slice(&self.zip_archive_entry.uncompressed_size.to_le_bytes());
+        }
+
+        if read_u32(header, 20).map(u64::from) == Some(ZIP64_FIELD_LIMIT) {
+            zip64_extra_field_data.extend_from_slice(&self.zip_archive_entry.compressed_size.to_le_bytes());
+        }
+
+        if entry_offset >= ZIP64_FIELD_LIMIT {
+            zip64_extra_field_data.extend_from_slice(&entry_offset.to_le_bytes());
+            central_directory_header[42..46].copy_from_slice(&(ZIP64_FIELD_LIMIT as u32).to_le_bytes());
+        } else {
+            central_directory_header[42..46].copy_from_slice(&(entry_offset as u32).to_le_bytes());
+        }
+
+        let mut extra_fields: Vec<ExtraField> = parse_extra_fields(&header[CENTRAL_DIRECTORY_HEADER_SIZE + file_name_length..comment_offset])
+            .into_iter()
+            .filter(|extra_field| extra_field.header_id != ZIP64_EXTRA_FIELD_ID)
+            .collect();
+
+        if !zip64_extra_field_data.is_empty() {
+            let version_needed_to_extract = read_u16(header, 6).unwrap_or(0).max(ZIP64_VERSION_NEEDED_TO_EXTRACT);
+
+            central_directory_header[6..8].copy_from_slice(&version_needed_to_extract.to_le_bytes());
+            extra_fields.insert(0, ExtraField { header_id: ZIP64_EXTRA_FIELD_ID, data: zip64_extra_field_data });
+        }
+
+        let (file_name, extra_fields, comment) = match renamed {
+            true => {
+                let general_purpose_bit_flag = self.get_renamed_general_purpose_bit_flag(read_u16(header, 8).unwrap_or(0), file_name);
+                central_directory_header[8..10].copy_from_slice(&general_purpose_bit_flag.to_le_bytes());
+
+                (file_name.as_bytes(), self.get_renamed_extra_fields(extra_fields, unicode_path_extra_field)?, self.zip_archive_entry.comment.as_bytes())
+            }
+            false => (&header[CENTRAL_DIRECTORY_HEADER_SIZE..CENTRAL_DIRECTORY_HEADER_SIZE + file_name_length], get_extra_field_bytes(extra_fields), &header[comment_offset..]),
+        };
+
+        if extra_fields.len() > EXTRA_FIELD_LENGTH_LIMIT {
+            return Err(ZipError::ExtraFieldTooLong { file_name: self.zip_archive_entry.file_name.clone() });
+        }
+
+        if comment.len() > COMMENT_LENGTH_LIMIT {
+            return Err(ZipError::FileCommentTooLong { file_name: self.zip_archive_entry.file_name.clone() });
+        }
+
+        central_directory_header[28..30].copy_from_slice(&(file_name.len() as u16).to_le_bytes());
+        central_directory_header[30..32].copy_from_slice(&(extra_fields.len() as u16).to_le_bytes());
+        central_directory_header[32..34].copy_from_slice(&(comment.len() as u16).to_le_bytes());
+        central_directory_header.extend_from_slice(file_name);
+        central_directory_header.extend_from_slice(&extra_fields);
+        central_directory_header.extend_from_slice(comment);
+
+        Ok(central_directory_header)
+    }
+
+    //A Unicode Path extra field carries the CRC-32 of the name it was written for, so it is replaced along with the name
+    fn get_renamed_extra_fields(&self, extra_fields: Vec<ExtraField>, unicode_path_extra_field: Option<&ExtraField>) -> Result<Vec<u8>, ZipError> {
+        let mut renamed_extra_fields: Vec<ExtraField> = extra_fields.into_iter()
+            .filter(|extra_field| extra_field.header_id != UNICODE_PATH_EXTRA_FIELD_ID)
+            .collect();
+
+        if let Some(unicode_path_extra_field) = unicode_path_extra_field {
+            renamed_extra_fields.insert(0, ExtraField { header_id: unicode_path_extra_field.header_id, data: unicode_path_extra_field.data.clone() });
+        }
+
+        let renamed_extra_fields = get_extra_field_bytes(renamed_extra_fields);
+
+        if renamed_extra_fields.len() > EXTRA_FIELD_LENGTH_LIMIT {
+            return Err(ZipError::ExtraFieldTooLong { file_name: self.zip_archive_entry.file_name.clone() });
+        }
+
+        Ok(renamed_extra_fields)
+    }
+
+    //The new name is written as UTF-8, and so is the comment, which may have been decoded with the IBM PC character set
+    fn get_renamed_general_purpose_bit_flag(&self, general_purpose_bit_flag: u16, file_name: &str) -> u16 {
+        if !file_name.is_ascii() || !self.zip_archive_entry.comment.is_ascii() {
+            return general_purpose_bit_flag | LANGUAGE_ENCODING_FLAG;
+        }
+
+        general_purpose_bit_flag & !LANGUAGE_ENCODING_FLAG
+    }
+}
+
+fn get_extra_field_bytes(extra_fields: Vec<ExtraField>) -> Vec<u8> {
+    extra_fields.iter().flat_map(ExtraField::get_bytes).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::zip_archive::ZipArchive;
+    use crate::zip_file::{FileDescriptor, ZipOptions};
+    use crate::zip_writer::ZipWriter;
+
+    fn create_zip_binary() -> Vec<u8> {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(1654424430000), ..ZipOptions::default() });
+
+        zip_writer.start_file("Capoo.txt", FileDescriptor::default()).unwrap();
+        zip_writer.write_all(b"Capoo is Hungry.").unwrap();
+
+        zip_writer.finish().unwrap()
+    }
+
+    #[test]
+    fn central_directory_header_with_zip64_offset() {
+        let zip_binary = create_zip_binary();
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+        let raw_entries = zip_archive.get_raw_entries().unwrap();
+
+        let central_directory_header = raw_entries[0].get_central_directory_header("Capoo.txt", None, 0x100000000).unwrap();
+        let (zip_archive_entry, _) = ZipArchiveEntry::from_central_directory_header(&central_directory_header, 0).unwrap();
+
+        assert_eq!([0x2D, 0x00], &central_directory_header[6..8]);
+        assert_eq!(0x100000000, zip_archive_entry.entry_offset);
+        assert_eq!(16, zip_archive_entry.uncompressed_size);
+    }
+
+    #[test]
+    fn renamed_local_file_record() {
+        let zip_binary = create_zip_binary();
+        let zip_archive = ZipArchive::new(&zip_binary).unwrap();
+        let raw_entries = zip_archive.get_raw_entries().unwrap();
+
+        let local_file_record = raw_entries[0].get_local_file_record("猫.txt", None).unwrap();
+
+        assert_eq!([0x00, 0x08], &local_file_record[6..8]);
+        assert_eq!([0x07, 0x00], &local_file_record[26..28]);
+        assert_eq!("猫.txt".as_bytes(), &local_file_record[30..37]);
+        assert_eq!(&raw_entries[0].local_file_record[39..], &local_file_record[37..]);
+    }
+}
\ No newline at end of file
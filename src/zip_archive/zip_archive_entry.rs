@@ -0,0 +1,287 @@
+use crate::date_time_converter::SystemTime;
+use crate::zip_archive::{decode_cp437, read_bytes, read_u16, read_u32};
+use crate::zip_error::ZipError;
+use crate::zip_file::extra_field::{parse_extended_timestamp_modified, parse_extra_fields, ExtraField, EXTENDED_TIMESTAMP_EXTRA_FIELD_ID, ZIP64_EXTRA_FIELD_ID};
+use crate::zip_file::{CENTRAL_DIRECTORY_HEADER_SIGNATURE, CENTRAL_DIRECTORY_HEADER_SIZE, LANGUAGE_ENCODING_FLAG, MS_DOS_DIRECTORY_ATTRIBUTE, ZIP64_FIELD_LIMIT};
+
+pub struct ZipArchiveEntry {
+    pub file_name: String,
+    pub compression_method: u16,
+    pub crc: u32,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub dos_time: u16,
+    pub dos_date: u16,
+    pub entry_offset: u64,
+    pub general_purpose_bit_flag: u16,
+    pub version_made_by: u16,
+    pub external_file_attributes: u32,
+    pub central_directory_extra_fields: Vec<ExtraField>,
+    pub comment: String,
+}
+
+impl ZipArchiveEntry {
+    //Returns the entry along with the size of its header, which is where the next header starts
+    pub(crate) fn from_central_directory_header(data: &[u8], offset: usize) -> Result<(ZipArchiveEntry, usize), ZipError> {
+        let header = read_bytes(data, offset, CENTRAL_DIRECTORY_HEADER_SIZE).ok_or(ZipError::InvalidCentralDirectory)?;
+
+        if read_u32(header, 0) != Some(CENTRAL_DIRECTORY_HEADER_SIGNATURE) {
+            return Err(ZipError::InvalidCentralDirectory);
+        }
+
+        let field = |field_offset: usize| read_u16(header, field_offset).unwrap_or(0);
+        let long_field = |field_offset: usize| read_u32(header, field_offset).unwrap_or(0);
+
+        let general_purpose_bit_flag = field(8);
+        let file_name_length = field(28) as usize;
+        let extra_field_length = field(30) as usize;
+        let comment_length = field(32) as usize;
+
+        let variable_offset = offset + CENTRAL_DIRECTORY_HEADER_SIZE;
+        let file_name = read_bytes(data, variable_offset, file_name_length).ok_or(ZipError::InvalidCentralDirectory)?;
+        let extra_fields = read_bytes(data, variable_offset + file_name_length, extra_field_length).ok_or(ZipError::InvalidCentralDirectory)?;
+        let comment = read_bytes(data, variable_offset + file_name_length + extra_field_length, comment_length).ok_or(ZipError::InvalidCentralDirectory)?;
+
+        let mut zip_archive_entry = ZipArchiveEntry {
+            file_name: decode_text(file_name, general_purpose_bit_flag),
+            compression_method: field(10),
+            crc: long_field(16),
+            compressed_size: u64::from(long_field(20)),
+            uncompressed_size: u64::from(long_field(24)),
+            dos_time: field(12),
+            dos_date: field(14),
+            entry_offset: u64::from(long_field(42)),
+            general_purpose_bit_flag,
+            version_made_by: field(4),
+            external_file_attributes: long_field(38),
+            central_directory_extra_fields: parse_extra_fields(extra_fields),
+            comment: decode_text(comment, general_purpose_bit_flag),
+        };
+
+        zip_archive_entry.apply_zip64_extra_field()?;
+
+        let header_size = CENTRAL_DIRECTORY_HEADER_SIZE + file_name_length + extra_field_length + comment_length;
+
+        Ok((zip_archive_entry, header_size))
+    }
+
+    //Only the fields left as placeholders in the header are present, in the order of the uncompressed size, compressed
+    //size and offset
+    fn apply_zip64_extra_field(&mut self) -> Result<(), ZipError> {
+        let mut zip64_fields: Vec<&mut u64> = Vec::new();
+
+        if self.uncompressed_size == ZIP64_FIELD_LIMIT {
+            zip64_fields.push(&mut self.uncompressed_size);
+        }
+
+        if self.compressed_size == ZIP64_FIELD_LIMIT {
+            zip64_fields.push(&mut self.compressed_size);
+        }
+
+        if self.entry_offset == ZIP64_FIELD_LIMIT {
+            zip64_fields.push(&mut self.entry_offset);
+        }
+
+        if zip64_fields.is_empty() {
+            return Ok(());
+        }
+
+        let zip64_extra_field = self.central_directory_extra_fields.iter()
+            .find(|extra_field| extra_field.header_id == ZIP64_EXTRA_FIELD_ID)
+            .ok_or(ZipError::InvalidCentralDirectory)?;
+
+        for (index, zip64_field) in zip64_fields.into_iter().enumerate() {
+            let value = read_bytes(&zip64_extra_field.data, index * 8, 8).ok_or(ZipError::InvalidCentralDirectory)?;
+
+            let mut value_bytes = [0; 8];
+            value_bytes.copy_from_slice(value);
+
+            *zip64_field = u64::from_le_bytes(value_bytes);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_directory(&self) -> bool {
+        self.file_name.ends_with('/') || self.external_file_attributes & MS_DOS_DIRECTORY_ATTRIBUTE != 0
+    }
+
+    //Renaming a folder also renames everything inside it, so a name matches the entry itself or any of its parent folders.
+    //Folders keep their trailing slash, or extractors would create a file in place of the folder
+    pub fn get_renamed_file_name(&self, file_name: &str, new_file_name: &str) -> Option<String> {
+        let folder_name = file_name.trim_end_matches('/');
+        let new_folder_name = new_file_name.trim_end_matches('/');
+
+        if self.file_name == file_name {
+            return match self.is_directory() {
+                true => Some(format!("{}/", new_folder_name)),
+                false => Some(new_folder_name.to_string()),
+            };
+        }
+
+        self.file_name
+            .strip_prefix(folder_name)
+            .filter(|remaining_file_name| !folder_name.is_empty() && remaining_file_name.starts_with('/'))
+            .map(|remaining_file_name| format!("{}{}", new_folder_name, remaining_file_name))
+    }
+
+    //The extended timestamp is in UTC and preferred, while MS-DOS times carry no time zone and are read in local time, which
+    //is how archives are stamped by default
+    pub fn get_last_modified(&self) -> i64 {
+        self.get_extended_last_modified().unwrap_or_else(|| self.get_dos_date_time().to_local_unix_time_millis())
+    }
+
+    pub fn get_extended_last_modified(&self) -> Option<i64> {
+        self.central_directory_extra_fields.iter()
+            .find(|extra_field| extra_field.header_id == EXTENDED_TIMESTAMP_EXTRA_FIELD_ID)
+            .and_then(parse_extended_timestamp_modified)
+    }
+
+    pub(crate) fn get_dos_date_time(&self) -> SystemTime {
+        SystemTime::from_dos_date_time(self.dos_date, self.dos_time)
+    }
+}
+
+//Without the language encoding flag, names and comments are encoded with the IBM PC character set
+fn decode_text(text: &[u8], general_purpose_bit_flag: u16) -> String {
+    if general_purpose_bit_flag & LANGUAGE_ENCODING_FLAG != 0 {
+        return String::from_utf8_lossy(text).into_owned();
+    }
+
+    decode_cp437(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_central_directory_header(file_name: &[u8], general_purpose_bit_flag: u16, extra_fields: &[u8]) -> Vec<u8> {
+        let mut header: Vec<u8> = vec![
+            0x50, 0x4B, 0x01, 0x02,     //central directory header signature
+            0x3F, 0x03,                 //version made by
+            0x14, 0x00,                 //version needed to extract
+        ];
+
+        header.extend_from_slice(&general_purpose_bit_flag.to_le_bytes());
+        header.extend_from_slice(&[
+            0x08, 0x00,                 //compression method
+            0x8F, 0x52,                 //last modified time
+            0xC5, 0x54,                 //last modified date
+            0x78, 0x56, 0x34, 0x12,     //crc
+            0xFF, 0xFF, 0xFF, 0xFF,     //compressed size
+            0x05, 0x00, 0x00, 0x00,     //uncompressed size
+        ]);
+        header.extend_from_slice(&(file_name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&(extra_fields.len() as u16).to_le_bytes());
+        header.extend_from_slice(&[
+            0x03, 0x00,                 //file comment length
+            0x00, 0x00,                 //disk number start
+            0x00, 0x00,                 //internal file attributes
+            0x00, 0x00, 0xA4, 0x81,     //external file attributes
+            0x10, 0x00, 0x00, 0x00,     //relative offset of local header
+        ]);
+        header.extend_from_slice(file_name);
+        header.extend_from_slice(extra_fields);
+        header.extend_from_slice(b"yow");
+
+        header
+    }
+
+    #[test]
+    fn entry_from_central_directory_header() {
+        let zip64_extra_field = [0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00];
+        let header = get_central_directory_header("カプー.txt".as_bytes(), 0x0800, &zip64_extra_field);
+
+        let (zip_archive_entry, header_size) = ZipArchiveEntry::from_central_directory_header(&header, 0).unwrap();
+
+        assert_eq!(header.len(), header_size);
+        assert_eq!("カプー.txt", zip_archive_entry.file_name);
+        assert_eq!(0x0008, zip_archive_entry.compression_method);
+        assert_eq!(0x12345678, zip_archive_entry.crc);
+        assert_eq!(0x100000003, zip_archive_entry.compressed_size);
+        assert_eq!(5, zip_archive_entry.uncompressed_size);
+        assert_eq!(0x10, zip_archive_entry.entry_offset);
+        assert_eq!(0x81A40000, zip_archive_entry.external_file_attributes);
+        assert_eq!("yow", zip_archive_entry.comment);
+        assert_eq!(1654424430000, zip_archive_entry.get_last_modified());
+        assert!(!zip_archive_entry.is_directory());
+    }
+
+    #[test]
+    fn last_modified_prefers_extended_timestamp() {
+        let extra_fields = [
+            0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x55, 0x54, 0x05, 0x00, 0x01, 0x2E, 0x8A, 0xA0, 0x62,
+        ];
+        let header = get_central_directory_header(b"Capoo.txt", 0, &extra_fields);
+
+        let (zip_archive_entry, _) = ZipArchiveEntry::from_central_directory_header(&header, 0).unwrap();
+
+        assert_eq!(Some(1654688302000), zip_archive_entry.get_extended_last_modified());
+        assert_eq!(1654688302000, zip_archive_entry.get_last_modified());
+    }
+
+    #[test]
+    fn entry_from_central_directory_header_with_ibm_pc_file_name() {
+        let zip64_extra_field = [0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let header = get_central_directory_header(&[0x43, 0x61, 0x66, 0x82, 0x2F], 0, &zip64_extra_field);
+
+        let (zip_archive_entry, _) = ZipArchiveEntry::from_central_directory_header(&header, 0).unwrap();
+
+        assert_eq!("Café/", zip_archive_entry.file_name);
+        assert!(zip_archive_entry.is_directory());
+    }
+
+    #[test]
+    fn entry_from_central_directory_header_without_zip64_extra_field() {
+        let header = get_central_directory_header(b"BugCat.txt", 0, &[]);
+
+        let zip_error = ZipArchiveEntry::from_central_directory_header(&header, 0).err();
+
+        assert_eq!(Some(ZipError::InvalidCentralDirectory), zip_error);
+    }
+
+    #[test]
+    fn renamed_file_name_of_file_and_folder() {
+        let header = get_central_directory_header(b"Folder/Capoo.txt", 0, &[0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        let (zip_archive_entry, _) = ZipArchiveEntry::from_central_directory_header(&header, 0).unwrap();
+
+        assert_eq!(Some(String::from("BugCat.txt")), zip_archive_entry.get_renamed_file_name("Folder/Capoo.txt", "BugCat.txt"));
+        assert_eq!(Some(String::from("Cats/Capoo.txt")), zip_archive_entry.get_renamed_file_name("Folder", "Cats"));
+        assert_eq!(Some(String::from("Cats/Capoo.txt")), zip_archive_entry.get_renamed_file_name("Folder/", "Cats/"));
+        assert_eq!(None, zip_archive_entry.get_renamed_file_name("Fold", "Cats"));
+        assert_eq!(Some(String::from("BugCat.txt")), zip_archive_entry.get_renamed_file_name("Folder/Capoo.txt", "BugCat.txt/"));
+        assert_eq!(None, zip_archive_entry.get_renamed_file_name("Capoo.txt", "BugCat.txt"));
+    }
+
+    #[test]
+    fn renamed_file_name_of_folder_entry_keeps_trailing_slash() {
+        let header = get_central_directory_header(b"Folder/", 0, &[0x01, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        let (zip_archive_entry, _) = ZipArchiveEntry::from_central_directory_header(&header, 0).unwrap();
+
+        assert_eq!(Some(String::from("Cats/")), zip_archive_entry.get_renamed_file_name("Folder/", "Cats"));
+        assert_eq!(Some(String::from("Cats/")), zip_archive_entry.get_renamed_file_name("Folder/", "Cats/"));
+        assert_eq!(Some(String::from("Cats/")), zip_archive_entry.get_renamed_file_name("Folder", "Cats"));
+    }
+
+    #[test]
+    fn entry_from_truncated_central_directory_header() {
+        let header = get_central_directory_header(b"BugCat.txt", 0, &[]);
+
+        let zip_error = ZipArchiveEntry::from_central_directory_header(&header[..50], 0).err();
+
+        assert_eq!(Some(ZipError::InvalidCentralDirectory), zip_error);
+    }
+
+    #[test]
+    fn entry_from_invalid_signature() {
+        let mut header = get_central_directory_header(b"BugCat.txt", 0, &[]);
+        header[2] = 0x03;
+
+        let zip_error = ZipArchiveEntry::from_central_directory_header(&header, 0).err();
+
+        assert_eq!(Some(ZipError::InvalidCentralDirectory), zip_error);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,194 @@
+use std::convert::TryFrom;
+
+use crate::crc_calculator::CrcCalculator;
+use crate::decompressor::Decompressor;
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::{read_bytes, read_u16, read_u32, ZipEntryExtractor};
+use crate::zip_error::ZipError;
+use crate::zip_file::{LOCAL_FILE_HEADER_SIGNATURE, LOCAL_FILE_HEADER_SIZE};
+
+const ENCRYPTION_FLAG: u16 = 1;
+const STORED_COMPRESSION_METHOD: u16 = 0x0000;
+const DEFLATE_COMPRESSION_METHOD: u16 = 0x0008;
+
+pub struct ZipEntryExtractorAdapter {
+    pub(crate) crc_calculator: Box<dyn CrcCalculator>,
+    pub(crate) decompressor: Box<dyn Decompressor>,
+}
+
+impl ZipEntryExtractor for ZipEntryExtractorAdapter {
+    fn extract_entry(&self, data: &[u8], zip_archive_entry: &ZipArchiveEntry) -> Result<Vec<u8>, ZipError> {
+        let file_name = || zip_archive_entry.file_name.clone();
+
+        if zip_archive_entry.general_purpose_bit_flag & ENCRYPTION_FLAG != 0 {
+            return Err(ZipError::EncryptedEntry { file_name: file_name() });
+        }
+
+        let compressed_data = get_compressed_data(data, zip_archive_entry)
+            .ok_or_else(|| ZipError::InvalidLocalFileHeader { file_name: file_name() })?;
+        let uncompressed_size = usize::try_from(zip_archive_entry.uncompressed_size)
+            .map_err(|_| ZipError::FileTooLarge { file_name: file_name() })?;
+
+        let uncompressed_data = match zip_archive_entry.compression_method {
+            STORED_COMPRESSION_METHOD => Some(compressed_data.to_vec()),
+            DEFLATE_COMPRESSION_METHOD => self.decompressor.decompress(compressed_data, uncompressed_size),
+            method_id => return Err(ZipError::UnsupportedCompressionMethod { file_name: file_name(), method_id }),
+        };
+
+        match uncompressed_data {
+            Some(uncompressed_data) if uncompressed_data.len() == uncompressed_size
+                && self.crc_calculator.calculate_crc32(&uncompressed_data) == zip_archive_entry.crc => Ok(uncompressed_data),
+            _ => Err(ZipError::CorruptedEntry { file_name: file_name() }),
+        }
+    }
+}
+
+//The sizes in the local file header may be zeroed by a data descriptor, so only its variable lengths are used to find
+//the data, and the sizes come from the central directory
+fn get_compressed_data<'a>(data: &'a [u8], zip_archive_entry: &ZipArchiveEntry) -> Option<&'a [u8]> {
+    let entry_offset = usize::try_from(zip_archive_entry.entry_offset).ok()?;
+
+    if read_u32(data, entry_offset)? != LOCAL_FILE_HEADER_SIGNATURE {
+        return None;
+    }
+
+    let file_name_length = read_u16(data, entry_offset + 26)? as usize;
+    let extra_field_length = read_u16(data, entry_offset + 28)? as usize;
+
+    let data_offset = entry_offset + LOCAL_FILE_HEADER_SIZE + file_name_length + extra_field_length;
+
+    read_bytes(data, data_offset, usize::try_from(zip_archive_entry.compressed_size).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeCrcCalculator {}
+
+    impl CrcCalculator for FakeCrcCalculator {
+        fn calculate_crc32(&self, _data: &[u8]) -> u32 {
+            0x11223344
+        }
+    }
+
+    struct FakeDecompressor {}
+
+    impl Decompressor for FakeDecompressor {
+        fn decompress(&self, data: &[u8], _uncompressed_size: usize) -> Option<Vec<u8>> {
+            Some([data, data].concat())
+        }
+    }
+
+    fn create_zip_entry_extractor() -> ZipEntryExtractorAdapter {
+        ZipEntryExtractorAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            decompressor: Box::new(FakeDecompressor {}),
+        }
+    }
+
+    fn create_zip_archive_entry(compression_method: u16, uncompressed_size: u64) -> ZipArchiveEntry {
+        ZipArchiveEntry {
+            file_name: String::from("Capoo.txt"),
+            compression_method,
+            crc: 0x11223344,
+            compressed_size: 3,
+            uncompressed_size,
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 2,
+            general_purpose_bit_flag: 0,
+            version_made_by: 0x003F,
+            external_file_attributes: 0,
+            central_directory_extra_fields: vec![],
+            comment: String::new(),
+        }
+    }
+
+    fn get_local_file_entry() -> Vec<u8> {
+        vec![
+            0xAA, 0xBB,                 //preceding data
+            0x50, 0x4B, 0x03, 0x04,     //local file header signature
+            0x14, 0x00,                 //version needed to extract
+            0x08, 0x00,                 //general purpose bit flag
+            0x08, 0x00,                 //compression method
+            0x00, 0x00,                 //last modified time
+            0x00, 0x00,                 //last modified date
+            0x00, 0x00, 0x00, 0x00,     //crc
+            0x00, 0x00, 0x00, 0x00,     //compressed size
+            0x00, 0x00, 0x00, 0x00,     //uncompressed size
+            0x01, 0x00,                 //file name length
+            0x02, 0x00,                 //extra field length
+            0x43,                       //file name
+            0xFF, 0xFF,                 //extra field
+            0x01, 0x02, 0x03,           //file data
+            0xCC,                       //following data
+        ]
+    }
+
+    #[test]
+    fn stored_entry_is_copied() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &create_zip_archive_entry(0x0000, 3));
+
+        assert_eq!(Ok(vec![0x01, 0x02, 0x03]), entry_data);
+    }
+
+    #[test]
+    fn deflated_entry_is_decompressed() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &create_zip_archive_entry(0x0008, 6));
+
+        assert_eq!(Ok(vec![0x01, 0x02, 0x03, 0x01, 0x02, 0x03]), entry_data);
+    }
+
+    #[test]
+    fn entry_with_mismatched_crc() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+        let zip_archive_entry = ZipArchiveEntry { crc: 0x12345678, ..create_zip_archive_entry(0x0000, 3) };
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &zip_archive_entry);
+
+        assert_eq!(Err(ZipError::CorruptedEntry { file_name: String::from("Capoo.txt") }), entry_data);
+    }
+
+    #[test]
+    fn entry_with_mismatched_size() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &create_zip_archive_entry(0x0008, 5));
+
+        assert_eq!(Err(ZipError::CorruptedEntry { file_name: String::from("Capoo.txt") }), entry_data);
+    }
+
+    #[test]
+    fn entry_with_unsupported_compression_method() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &create_zip_archive_entry(0x000E, 3));
+
+        assert_eq!(Err(ZipError::UnsupportedCompressionMethod { file_name: String::from("Capoo.txt"), method_id: 0x000E }), entry_data);
+    }
+
+    #[test]
+    fn encrypted_entry() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+        let zip_archive_entry = ZipArchiveEntry { general_purpose_bit_flag: 0x0001, ..create_zip_archive_entry(0x0000, 3) };
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &zip_archive_entry);
+
+        assert_eq!(Err(ZipError::EncryptedEntry { file_name: String::from("Capoo.txt") }), entry_data);
+    }
+
+    #[test]
+    fn entry_with_invalid_local_file_header() {
+        let zip_entry_extractor = create_zip_entry_extractor();
+        let zip_archive_entry = ZipArchiveEntry { entry_offset: 0, ..create_zip_archive_entry(0x0000, 3) };
+
+        let entry_data = zip_entry_extractor.extract_entry(&get_local_file_entry(), &zip_archive_entry);
+
+        assert_eq!(Err(ZipError::InvalidLocalFileHeader { file_name: String::from("Capoo.txt") }), entry_data);
+    }
+}
\ No newline at end of file
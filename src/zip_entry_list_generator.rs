@@ -0,0 +1,34 @@
+use js_sys::{Array, Date, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::ZipArchive;
+
+pub fn generate_zip_entry_list(zip_archive: &ZipArchive) -> Array {
+    let zip_entry_list = Array::new();
+
+    for zip_archive_entry in zip_archive.get_entries() {
+        zip_entry_list.push(&get_zip_entry_object(zip_archive_entry));
+    }
+
+    zip_entry_list
+}
+
+fn get_zip_entry_object(zip_archive_entry: &ZipArchiveEntry) -> Object {
+    let zip_entry_object = Object::new();
+    let last_modified = Date::new(&JsValue::from(zip_archive_entry.get_last_modified() as f64));
+
+    set_property(&zip_entry_object, "name", &JsValue::from(zip_archive_entry.file_name.as_str()));
+    set_property(&zip_entry_object, "size", &JsValue::from(zip_archive_entry.uncompressed_size as f64));
+    set_property(&zip_entry_object, "compressedSize", &JsValue::from(zip_archive_entry.compressed_size as f64));
+    set_property(&zip_entry_object, "lastModified", &last_modified);
+    set_property(&zip_entry_object, "directory", &JsValue::from(zip_archive_entry.is_directory()));
+    set_property(&zip_entry_object, "crc", &JsValue::from(zip_archive_entry.crc));
+    set_property(&zip_entry_object, "comment", &JsValue::from(zip_archive_entry.comment.as_str()));
+
+    zip_entry_object
+}
+
+fn set_property(object: &Object, key: &str, value: &JsValue) {
+    Reflect::set(object, &JsValue::from(key), value).unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,211 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug, PartialEq)]
+pub enum ZipError {
+    FileNameTooLong { file_name: String },
+    ExtraFieldTooLong { file_name: String },
+    FileCommentTooLong { file_name: String },
+    ReservedExtraField { file_name: String, header_id: u16 },
+    FileTooLarge { file_name: String },
+    StreamedFileTooLarge { file_name: String },
+    OffsetOverflow,
+    ArchiveCommentTooLong,
+    ArchiveCommentContainsSignature,
+    Io { kind: io::ErrorKind, message: String },
+    EndOfCentralDirectoryNotFound,
+    MultiDiskArchive,
+    InvalidCentralDirectory,
+    InvalidLocalFileHeader { file_name: String },
+    EncryptedEntry { file_name: String },
+    UnsupportedCompressionMethod { file_name: String, method_id: u16 },
+    CorruptedEntry { file_name: String },
+    EntryNotFound { pattern: String },
+    InvalidCompressionMethod { compression: String },
+    InvalidExtraField { file_name: String },
+    UnsafeEntryName { file_name: String },
+    ConflictingEntryName { file_name: String },
+    InvalidEntryName { file_name: String },
+}
+
+impl Display for ZipError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZipError::FileNameTooLong { file_name } => {
+                write!(formatter, "The file name {} exceeds the maximum length of 65535 bytes", file_name)
+            }
+            ZipError::ExtraFieldTooLong { file_name } => {
+                write!(formatter, "The extra fields of {} exceed the maximum length of 65535 bytes", file_name)
+            }
+            ZipError::FileCommentTooLong { file_name } => {
+                write!(formatter, "The comment of {} exceeds the maximum length of 65535 bytes", file_name)
+            }
+            ZipError::ReservedExtraField { file_name, header_id } => {
+                write!(formatter, "The extra field 0x{:04X} of {} is reserved for the zip format", header_id, file_name)
+            }
+            ZipError::FileTooLarge { file_name } => {
+                write!(formatter, "The file {} is too large to be extracted into memory", file_name)
+            }
+            ZipError::StreamedFileTooLarge { file_name } => {
+                write!(formatter, "The file {} reached 4 GiB after its header was written without ZIP64, so it can only be streamed with force_zip64", file_name)
+            }
+            ZipError::OffsetOverflow => {
+                write!(formatter, "The archive is larger than a zip file can describe")
+            }
+            ZipError::ArchiveCommentTooLong => {
+                write!(formatter, "The archive comment exceeds the maximum length of 65535 bytes")
+            }
+            ZipError::ArchiveCommentContainsSignature => {
+                write!(formatter, "The archive comment contains the end of central directory signature")
+            }
+            ZipError::Io { message, .. } => {
+                write!(formatter, "The archive could not be written: {}", message)
+            }
+            ZipError::EndOfCentralDirectoryNotFound => {
+                write!(formatter, "The end of central directory record could not be found, so the data is not a zip archive")
+            }
+            ZipError::MultiDiskArchive => {
+                write!(formatter, "Archives split across multiple disks are not supported")
+            }
+            ZipError::InvalidCentralDirectory => {
+                write!(formatter, "The central directory of the archive is truncated or corrupted")
+            }
+            ZipError::InvalidLocalFileHeader { file_name } => {
+                write!(formatter, "The local file header of {} is truncated or corrupted", file_name)
+            }
+            ZipError::EncryptedEntry { file_name } => {
+                write!(formatter, "The entry {} is encrypted, which is not supported", file_name)
+            }
+            ZipError::UnsupportedCompressionMethod { file_name, method_id } => {
+                write!(formatter, "The entry {} uses the unsupported compression method {}", file_name, method_id)
+            }
+            ZipError::CorruptedEntry { file_name } => {
+                write!(formatter, "The entry {} is corrupted, as its data does not match its CRC-32 and size", file_name)
+            }
+            ZipError::EntryNotFound { pattern } => {
+                write!(formatter, "No file in the archive matches {}", pattern)
+            }
+            ZipError::UnsafeEntryName { file_name } => {
+                write!(formatter, "The entry {} contains a ., .. or __proto__ path segment, which cannot be extracted", file_name)
+            }
+            ZipError::ConflictingEntryName { file_name } => {
+                write!(formatter, "The entry {} has the same name as another file or folder in the archive", file_name)
+            }
+            ZipError::InvalidEntryName { file_name } => {
+                write!(formatter, "The entry name \"{}\" is empty or starts with /, which cannot be stored in the archive", file_name)
+            }
+            ZipError::InvalidExtraField { file_name } => {
+                write!(formatter, "An extra field of {} needs a header id up to 0xFFFF and at most 65535 bytes of Uint8Array data", file_name)
+            }
+            ZipError::InvalidCompressionMethod { compression } => {
+                write!(formatter, "The compression {} is not supported, use \"deflate\" or \"store\"", compression)
+            }
+        }
+    }
+}
+
+impl Error for ZipError {}
+
+impl From<io::Error> for ZipError {
+    fn from(error: io::Error) -> Self {
+        ZipError::Io { kind: error.kind(), message: error.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_too_long_message_names_the_file() {
+        let zip_error = ZipError::FileNameTooLong { file_name: String::from("BugCat.txt") };
+
+        assert_eq!("The file name BugCat.txt exceeds the maximum length of 65535 bytes", zip_error.to_string());
+    }
+
+    #[test]
+    fn file_too_large_message_names_the_file() {
+        let zip_error = ZipError::FileTooLarge { file_name: String::from("FoamCat.mp4") };
+
+        assert_eq!("The file FoamCat.mp4 is too large to be extracted into memory", zip_error.to_string());
+    }
+
+    #[test]
+    fn streamed_file_too_large_message_names_the_file() {
+        let zip_error = ZipError::StreamedFileTooLarge { file_name: String::from("FoamCat.mp4") };
+
+        assert_eq!("The file FoamCat.mp4 reached 4 GiB after its header was written without ZIP64, so it can only be streamed with force_zip64", zip_error.to_string());
+    }
+
+    #[test]
+    fn reserved_extra_field_message_names_the_header_id() {
+        let zip_error = ZipError::ReservedExtraField { file_name: String::from("BugCat.txt"), header_id: 0x0001 };
+
+        assert_eq!("The extra field 0x0001 of BugCat.txt is reserved for the zip format", zip_error.to_string());
+    }
+
+    #[test]
+    fn archive_comment_too_long_message_states_the_limit() {
+        let zip_error = ZipError::ArchiveCommentTooLong;
+
+        assert_eq!("The archive comment exceeds the maximum length of 65535 bytes", zip_error.to_string());
+    }
+
+    #[test]
+    fn file_comment_too_long_message_names_the_file() {
+        let zip_error = ZipError::FileCommentTooLong { file_name: String::from("BugCat.txt") };
+
+        assert_eq!("The comment of BugCat.txt exceeds the maximum length of 65535 bytes", zip_error.to_string());
+    }
+
+    #[test]
+    fn unsafe_entry_name_message_names_the_entry() {
+        let zip_error = ZipError::UnsafeEntryName { file_name: String::from("__proto__/BugCat.txt") };
+
+        assert_eq!("The entry __proto__/BugCat.txt contains a ., .. or __proto__ path segment, which cannot be extracted", zip_error.to_string());
+    }
+
+    #[test]
+    fn conflicting_entry_name_message_names_the_entry() {
+        let zip_error = ZipError::ConflictingEntryName { file_name: String::from("Docs/BugCat.txt") };
+
+        assert_eq!("The entry Docs/BugCat.txt has the same name as another file or folder in the archive", zip_error.to_string());
+    }
+
+    #[test]
+    fn invalid_entry_name_message_names_the_entry() {
+        let zip_error = ZipError::InvalidEntryName { file_name: String::from("/BugCat.txt") };
+
+        assert_eq!("The entry name \"/BugCat.txt\" is empty or starts with /, which cannot be stored in the archive", zip_error.to_string());
+    }
+
+    #[test]
+    fn invalid_extra_field_message_names_the_file() {
+        let zip_error = ZipError::InvalidExtraField { file_name: String::from("BugCat.txt") };
+
+        assert_eq!("An extra field of BugCat.txt needs a header id up to 0xFFFF and at most 65535 bytes of Uint8Array data", zip_error.to_string());
+    }
+
+    #[test]
+    fn invalid_compression_method_message_names_the_compression() {
+        let zip_error = ZipError::InvalidCompressionMethod { compression: String::from("deflat") };
+
+        assert_eq!("The compression deflat is not supported, use \"deflate\" or \"store\"", zip_error.to_string());
+    }
+
+    #[test]
+    fn io_error_converts_to_zip_error() {
+        let zip_error = ZipError::from(io::Error::new(io::ErrorKind::WriteZero, "disk full"));
+
+        assert_eq!(ZipError::Io { kind: io::ErrorKind::WriteZero, message: String::from("disk full") }, zip_error);
+        assert_eq!("The archive could not be written: disk full", zip_error.to_string());
+    }
+
+    #[test]
+    fn corrupted_entry_message_names_the_entry() {
+        let zip_error = ZipError::CorruptedEntry { file_name: String::from("BugCat.txt") };
+
+        assert_eq!("The entry BugCat.txt is corrupted, as its data does not match its CRC-32 and size", zip_error.to_string());
+    }
+}
\ No newline at end of file
@@ -1,8 +1,123 @@
-use std::collections::HashMap;
+use crate::compressor::DEFAULT_COMPRESSION_LEVEL;
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::ZipArchive;
+use crate::zip_error::ZipError;
+use crate::zip_file::extra_field::ExtraField;
 
-mod zip_file_entry;
+pub mod extra_field;
+pub(crate) mod zip_file_entry;
 pub mod zip_blob_factory;
 
+pub(crate) const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034B50;
+pub(crate) const CENTRAL_DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014B50;
+pub(crate) const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054B50;
+pub(crate) const ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06064B50;
+pub(crate) const ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIGNATURE: u32 = 0x07064B50;
+pub(crate) const LOCAL_FILE_HEADER_SIZE: usize = 30;
+pub(crate) const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+pub(crate) const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+pub(crate) const ZIP64_END_OF_CENTRAL_DIRECTORY_LOCATOR_SIZE: usize = 20;
+pub(crate) const ZIP64_FIELD_LIMIT: u64 = 0xFFFFFFFF;
+pub(crate) const ZIP64_VERSION_NEEDED_TO_EXTRACT: u16 = 0x002D;
+pub(crate) const LANGUAGE_ENCODING_FLAG: u16 = 1 << 11;
+pub(crate) const FILE_NAME_LENGTH_LIMIT: usize = 0xFFFF;
+pub(crate) const EXTRA_FIELD_LENGTH_LIMIT: usize = 0xFFFF;
+pub(crate) const COMMENT_LENGTH_LIMIT: usize = 0xFFFF;
+pub(crate) const MS_DOS_DIRECTORY_ATTRIBUTE: u32 = 0x10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompressionMethod {
+    Stored,
+    Deflate,
+}
+
+impl CompressionMethod {
+    pub fn get_method_id(&self) -> u16 {
+        match self {
+            CompressionMethod::Stored => 0x0000,
+            CompressionMethod::Deflate => 0x0008,
+        }
+    }
+
+    pub fn get_version_needed_to_extract(&self) -> u16 {
+        match self {
+            CompressionMethod::Stored => 0x000A,
+            CompressionMethod::Deflate => 0x0014,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EntryType {
+    #[default]
+    File,
+    Directory,
+    Symlink,
+}
+
+pub struct ZipOptions {
+    pub compression_method: CompressionMethod,
+    pub compression_level: u8,
+    pub skip_compression: Vec<String>,
+    pub force_zip64: bool,
+    pub data_descriptor: bool,
+    pub data_descriptor_signature: bool,
+    pub unicode_path_extra_field: bool,
+    pub directory_entries: bool,
+    pub timestamp: Option<i64>,
+    pub extended_timestamp_extra_field: bool,
+    pub ntfs_extra_field: bool,
+    pub unix_permissions: bool,
+    pub comment: String,
+}
+
+impl Default for ZipOptions {
+    fn default() -> Self {
+        ZipOptions {
+            compression_method: CompressionMethod::Deflate,
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+            skip_compression: Vec::new(),
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: true,
+            unicode_path_extra_field: false,
+            directory_entries: true,
+            timestamp: None,
+            extended_timestamp_extra_field: false,
+            ntfs_extra_field: false,
+            unix_permissions: false,
+            comment: String::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FileDescriptor {
+    pub body: Vec<u8>,
+    pub compression_method: Option<CompressionMethod>,
+    pub compression_level: Option<u8>,
+    pub mime_type: Option<String>,
+    pub entry_type: EntryType,
+    pub last_modified: Option<i64>,
+    pub last_accessed: Option<i64>,
+    pub created: Option<i64>,
+    pub unix_mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub local_extra_fields: Vec<ExtraField>,
+    pub central_directory_extra_fields: Vec<ExtraField>,
+    pub comment: Option<String>,
+}
+
+//Milliseconds since the Unix epoch in UTC
+pub struct FileTimes {
+    pub modified: i64,
+    pub accessed: Option<i64>,
+    pub created: Option<i64>,
+}
+
 pub trait ZipBlobFactory {
-    fn create_zip_binary(&self, directory_mapping: HashMap<String, Vec<u8>>) -> Box<[u8]>;
+    fn create_zip_binary(&self, directory_mapping: Vec<(String, FileDescriptor)>) -> Result<Box<[u8]>, ZipError>;
+    fn append_zip_binary(&self, zip_archive: &ZipArchive, directory_mapping: Vec<(String, FileDescriptor)>) -> Result<Box<[u8]>, ZipError>;
+    fn rewrite_zip_binary(&self, zip_archive: &ZipArchive, get_new_file_name: &dyn Fn(&ZipArchiveEntry) -> Option<String>) -> Result<Box<[u8]>, ZipError>;
 }
\ No newline at end of file
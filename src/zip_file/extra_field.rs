@@ -0,0 +1,373 @@
+use std::convert::TryFrom;
+
+use crate::zip_file::FileTimes;
+
+pub const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
+pub const UNICODE_PATH_EXTRA_FIELD_ID: u16 = 0x7075;
+pub const EXTENDED_TIMESTAMP_EXTRA_FIELD_ID: u16 = 0x5455;
+pub const NTFS_EXTRA_FIELD_ID: u16 = 0x000A;
+pub const UNIX_OWNER_EXTRA_FIELD_ID: u16 = 0x7875;
+
+const MODIFICATION_TIME_FLAG: u8 = 1;
+const ACCESS_TIME_FLAG: u8 = 1 << 1;
+const CREATION_TIME_FLAG: u8 = 1 << 2;
+
+const NTFS_TIMESTAMP_TAG: u16 = 0x0001;
+const NTFS_TIMESTAMP_SIZE: u16 = 24;
+const NTFS_EPOCH_OFFSET_MILLIS: i64 = 11644473600000;
+const NTFS_TICKS_PER_MILLISECOND: u64 = 10000;
+
+pub struct ExtraField {
+    pub header_id: u16,
+    pub data: Vec<u8>,
+}
+
+impl ExtraField {
+    pub fn get_size(&self) -> usize {
+        4 + self.data.len()
+    }
+
+    pub fn get_bytes(&self) -> Vec<u8> {
+        let mut extra_field: Vec<u8> = Vec::with_capacity(self.get_size());
+
+        let data_size = self.data.len() as u16;
+
+        extra_field.extend_from_slice(&self.header_id.to_le_bytes());
+        extra_field.extend_from_slice(&data_size.to_le_bytes());
+        extra_field.extend_from_slice(&self.data);
+
+        extra_field
+    }
+}
+
+//Reads the extra fields of a header back, ignoring any trailing bytes too short to be a field
+pub fn parse_extra_fields(extra_field_bytes: &[u8]) -> Vec<ExtraField> {
+    let mut extra_fields: Vec<ExtraField> = Vec::new();
+    let mut offset = 0;
+
+    while offset + 4 <= extra_field_bytes.len() {
+        let header_id = u16::from_le_bytes([extra_field_bytes[offset], extra_field_bytes[offset + 1]]);
+        let data_size = u16::from_le_bytes([extra_field_bytes[offset + 2], extra_field_bytes[offset + 3]]) as usize;
+        let data_end = (offset + 4 + data_size).min(extra_field_bytes.len());
+
+        extra_fields.push(ExtraField {
+            header_id,
+            data: extra_field_bytes[offset + 4..data_end].to_vec(),
+        });
+
+        offset += 4 + data_size;
+    }
+
+    extra_fields
+}
+
+pub fn create_unicode_path_extra_field(file_name: &str, file_name_crc: u32) -> ExtraField {
+    let mut data: Vec<u8> = vec![
+        0x01,                       // unicode path extra field version
+    ];
+
+    data.extend_from_slice(&file_name_crc.to_le_bytes());
+    data.extend_from_slice(file_name.as_bytes());
+
+    ExtraField {
+        header_id: UNICODE_PATH_EXTRA_FIELD_ID,
+        data,
+    }
+}
+
+//Times that do not fit in the field's signed 32 bit seconds are left out
+pub fn create_local_extended_timestamp_extra_field(file_times: &FileTimes) -> ExtraField {
+    let mut data: Vec<u8> = vec![get_extended_timestamp_flags(file_times)];
+
+    for time in [Some(file_times.modified), file_times.accessed, file_times.created] {
+        if let Some(time) = time.and_then(get_extended_timestamp) {
+            data.extend_from_slice(&time.to_le_bytes());
+        }
+    }
+
+    ExtraField {
+        header_id: EXTENDED_TIMESTAMP_EXTRA_FIELD_ID,
+        data,
+    }
+}
+
+//The central directory only ever holds the modification time, but keeps the flags of the local header
+pub fn create_central_directory_extended_timestamp_extra_field(file_times: &FileTimes) -> ExtraField {
+    let mut data: Vec<u8> = vec![get_extended_timestamp_flags(file_times)];
+
+    if let Some(modified) = get_extended_timestamp(file_times.modified) {
+        data.extend_from_slice(&modified.to_le_bytes());
+    }
+
+    ExtraField {
+        header_id: EXTENDED_TIMESTAMP_EXTRA_FIELD_ID,
+        data,
+    }
+}
+
+fn get_extended_timestamp_flags(file_times: &FileTimes) -> u8 {
+    let mut flags: u8 = 0;
+
+    if get_extended_timestamp(file_times.modified).is_some() {
+        flags |= MODIFICATION_TIME_FLAG;
+    }
+
+    if file_times.accessed.and_then(get_extended_timestamp).is_some() {
+        flags |= ACCESS_TIME_FLAG;
+    }
+
+    if file_times.created.and_then(get_extended_timestamp).is_some() {
+        flags |= CREATION_TIME_FLAG;
+    }
+
+    flags
+}
+
+fn get_extended_timestamp(unix_time_millis: i64) -> Option<i32> {
+    i32::try_from(unix_time_millis.div_euclid(1000)).ok()
+}
+
+//The modification time comes first when its flag is set, as signed seconds since the Unix epoch
+pub fn parse_extended_timestamp_modified(extra_field: &ExtraField) -> Option<i64> {
+    match extra_field.data.as_slice() {
+        [flags, b0, b1, b2, b3, ..] if flags & MODIFICATION_TIME_FLAG != 0 => {
+            Some(i64::from(i32::from_le_bytes([*b0, *b1, *b2, *b3])) * 1000)
+        }
+        _ => None,
+    }
+}
+
+//Windows expects all three times, so the modification time stands in for any that are unknown
+pub fn create_ntfs_extra_field(file_times: &FileTimes) -> ExtraField {
+    let modified = get_ntfs_file_time(file_times.modified);
+    let accessed = file_times.accessed.map_or(modified, get_ntfs_file_time);
+    let created = file_times.created.map_or(modified, get_ntfs_file_time);
+
+    let mut data: Vec<u8> = vec![
+        0x00, 0x00, 0x00, 0x00,     // reserved
+    ];
+
+    data.extend_from_slice(&NTFS_TIMESTAMP_TAG.to_le_bytes());
+    data.extend_from_slice(&NTFS_TIMESTAMP_SIZE.to_le_bytes());
+    data.extend_from_slice(&modified.to_le_bytes());
+    data.extend_from_slice(&accessed.to_le_bytes());
+    data.extend_from_slice(&created.to_le_bytes());
+
+    ExtraField {
+        header_id: NTFS_EXTRA_FIELD_ID,
+        data,
+    }
+}
+
+pub fn create_local_unix_owner_extra_field(uid: u32, gid: u32) -> ExtraField {
+    let mut data: Vec<u8> = vec![
+        0x01,                       // unix owner extra field version
+        0x04,                       // uid size
+    ];
+
+    data.extend_from_slice(&uid.to_le_bytes());
+    data.push(0x04);                // gid size
+    data.extend_from_slice(&gid.to_le_bytes());
+
+    ExtraField {
+        header_id: UNIX_OWNER_EXTRA_FIELD_ID,
+        data,
+    }
+}
+
+//Info-ZIP only reads the owner from the local header, leaving an empty field in the central directory
+pub fn create_central_directory_unix_owner_extra_field() -> ExtraField {
+    ExtraField {
+        header_id: UNIX_OWNER_EXTRA_FIELD_ID,
+        data: Vec::new(),
+    }
+}
+
+//NTFS file times count 100 nanosecond intervals since the start of 1601
+fn get_ntfs_file_time(unix_time_millis: i64) -> u64 {
+    let millis_since_1601 = u64::try_from(unix_time_millis.saturating_add(NTFS_EPOCH_OFFSET_MILLIS)).unwrap_or(0);
+
+    millis_since_1601.saturating_mul(NTFS_TICKS_PER_MILLISECOND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_field_size_includes_header() {
+        let extra_field = ExtraField {
+            header_id: 0xCAFE,
+            data: vec![0; 7],
+        };
+
+        assert_eq!(11, extra_field.get_size());
+    }
+
+    #[test]
+    fn extra_field_bytes() {
+        let extra_field = ExtraField {
+            header_id: 0xCAFE,
+            data: vec![0x11, 0x22, 0x33],
+        };
+
+        assert_eq!(vec![0xFE, 0xCA, 0x03, 0x00, 0x11, 0x22, 0x33], extra_field.get_bytes());
+    }
+
+    #[test]
+    fn parse_extra_fields_from_bytes() {
+        let extra_fields = parse_extra_fields(&[0xFE, 0xCA, 0x03, 0x00, 0x11, 0x22, 0x33, 0x75, 0x78, 0x00, 0x00, 0x01]);
+
+        assert_eq!(2, extra_fields.len());
+        assert_eq!(0xCAFE, extra_fields[0].header_id);
+        assert_eq!(vec![0x11, 0x22, 0x33], extra_fields[0].data);
+        assert_eq!(0x7875, extra_fields[1].header_id);
+        assert!(extra_fields[1].data.is_empty());
+    }
+
+    #[test]
+    fn parse_extended_timestamp_modification_time() {
+        let extra_field = create_central_directory_extended_timestamp_extra_field(&FileTimes {
+            modified: 1654688302000,
+            accessed: Some(1654688303000),
+            created: None,
+        });
+
+        assert_eq!(Some(1654688302000), parse_extended_timestamp_modified(&extra_field));
+        assert_eq!(None, parse_extended_timestamp_modified(&ExtraField { header_id: EXTENDED_TIMESTAMP_EXTRA_FIELD_ID, data: vec![0x02] }));
+        assert_eq!(None, parse_extended_timestamp_modified(&ExtraField { header_id: EXTENDED_TIMESTAMP_EXTRA_FIELD_ID, data: vec![0x01, 0x00] }));
+    }
+
+    #[test]
+    fn unicode_path_extra_field() {
+        let extra_field = create_unicode_path_extra_field("猫.txt", 0x11223344);
+
+        assert_eq!(UNICODE_PATH_EXTRA_FIELD_ID, extra_field.header_id);
+        assert_eq!(0x01, extra_field.data[0]);
+        assert_eq!([0x44, 0x33, 0x22, 0x11], &extra_field.data[1..5]);
+        assert_eq!("猫.txt".as_bytes(), &extra_field.data[5..]);
+    }
+
+    #[test]
+    fn local_extended_timestamp_extra_field_with_modification_time() {
+        let file_times = FileTimes {
+            modified: 1654688302000,
+            accessed: None,
+            created: None,
+        };
+
+        let extra_field = create_local_extended_timestamp_extra_field(&file_times);
+
+        assert_eq!(0x5455, extra_field.header_id);
+        assert_eq!(vec![0x01, 0x2E, 0x8A, 0xA0, 0x62], extra_field.data);
+    }
+
+    #[test]
+    fn local_extended_timestamp_extra_field_with_all_times() {
+        let file_times = FileTimes {
+            modified: 1654688302000,
+            accessed: Some(287454020000),
+            created: Some(-1000),
+        };
+
+        let extra_field = create_local_extended_timestamp_extra_field(&file_times);
+
+        assert_eq!(vec![
+            0x07,
+            0x2E, 0x8A, 0xA0, 0x62,
+            0x44, 0x33, 0x22, 0x11,
+            0xFF, 0xFF, 0xFF, 0xFF,
+        ], extra_field.data);
+    }
+
+    #[test]
+    fn local_extended_timestamp_extra_field_without_unrepresentable_times() {
+        let file_times = FileTimes {
+            modified: 1654688302000,
+            accessed: Some(0x1_0000_0000 * 1000),
+            created: Some(287454020000),
+        };
+
+        let extra_field = create_local_extended_timestamp_extra_field(&file_times);
+
+        assert_eq!(vec![0x05, 0x2E, 0x8A, 0xA0, 0x62, 0x44, 0x33, 0x22, 0x11], extra_field.data);
+    }
+
+    #[test]
+    fn central_directory_extended_timestamp_extra_field_only_has_modification_time() {
+        let file_times = FileTimes {
+            modified: 1654688302000,
+            accessed: Some(287454020000),
+            created: Some(287454020000),
+        };
+
+        let extra_field = create_central_directory_extended_timestamp_extra_field(&file_times);
+
+        assert_eq!(0x5455, extra_field.header_id);
+        assert_eq!(vec![0x07, 0x2E, 0x8A, 0xA0, 0x62], extra_field.data);
+    }
+
+    #[test]
+    fn ntfs_extra_field_with_all_times() {
+        let file_times = FileTimes {
+            modified: 1654424430123,
+            accessed: Some(0),
+            created: Some(-11644473600000),
+        };
+
+        let extra_field = create_ntfs_extra_field(&file_times);
+
+        assert_eq!(0x000A, extra_field.header_id);
+        assert_eq!(vec![
+            0x00, 0x00, 0x00, 0x00,
+            0x01, 0x00, 0x18, 0x00,
+            0xB0, 0x6F, 0xE5, 0xE1, 0xC5, 0x78, 0xD8, 0x01,
+            0x00, 0x80, 0x3E, 0xD5, 0xDE, 0xB1, 0x9D, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ], extra_field.data);
+    }
+
+    #[test]
+    fn ntfs_extra_field_uses_modification_time_for_unknown_times() {
+        let file_times = FileTimes {
+            modified: 0,
+            accessed: None,
+            created: None,
+        };
+
+        let extra_field = create_ntfs_extra_field(&file_times);
+
+        assert_eq!(&[0x00, 0x80, 0x3E, 0xD5, 0xDE, 0xB1, 0x9D, 0x01], &extra_field.data[8..16]);
+        assert_eq!(&extra_field.data[8..16], &extra_field.data[16..24]);
+        assert_eq!(&extra_field.data[8..16], &extra_field.data[24..32]);
+    }
+
+    #[test]
+    fn ntfs_extra_field_clamps_times_before_1601() {
+        let file_times = FileTimes {
+            modified: i64::MIN,
+            accessed: None,
+            created: None,
+        };
+
+        let extra_field = create_ntfs_extra_field(&file_times);
+
+        assert_eq!(&[0x00; 8], &extra_field.data[8..16]);
+    }
+
+    #[test]
+    fn local_unix_owner_extra_field() {
+        let extra_field = create_local_unix_owner_extra_field(1000, 0x11223344);
+
+        assert_eq!(0x7875, extra_field.header_id);
+        assert_eq!(vec![0x01, 0x04, 0xE8, 0x03, 0x00, 0x00, 0x04, 0x44, 0x33, 0x22, 0x11], extra_field.data);
+    }
+
+    #[test]
+    fn central_directory_unix_owner_extra_field_is_empty() {
+        let extra_field = create_central_directory_unix_owner_extra_field();
+
+        assert_eq!(0x7875, extra_field.header_id);
+        assert_eq!(vec![0x75, 0x78, 0x00, 0x00], extra_field.get_bytes());
+    }
+}
\ No newline at end of file
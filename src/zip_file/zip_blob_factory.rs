@@ -1,93 +1,661 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 
 use crate::CrcCalculator;
+use crate::compressor::Compressor;
 use crate::date_time_converter::DosDateTimeCalculator;
-use crate::zip_file::zip_file_entry::ZipFileEntry;
-use crate::zip_file::ZipBlobFactory;
+use crate::zip_archive::zip_archive_entry::ZipArchiveEntry;
+use crate::zip_archive::ZipArchive;
+use crate::zip_error::ZipError;
+use crate::zip_file::extra_field::{
+    create_central_directory_extended_timestamp_extra_field, create_local_extended_timestamp_extra_field,
+    create_central_directory_unix_owner_extra_field, create_local_unix_owner_extra_field, create_ntfs_extra_field,
+    create_unicode_path_extra_field, ExtraField, EXTENDED_TIMESTAMP_EXTRA_FIELD_ID, NTFS_EXTRA_FIELD_ID, UNICODE_PATH_EXTRA_FIELD_ID,
+    UNIX_OWNER_EXTRA_FIELD_ID, ZIP64_EXTRA_FIELD_ID,
+};
+use crate::zip_file::zip_file_entry::{VERSION_MADE_BY_MS_DOS, VERSION_MADE_BY_UNIX, ZipFileEntry};
+use crate::zip_file::{
+    CompressionMethod, EntryType, FileDescriptor, FileTimes, ZipBlobFactory, ZipOptions, COMMENT_LENGTH_LIMIT, END_OF_CENTRAL_DIRECTORY_SIGNATURE,
+    EXTRA_FIELD_LENGTH_LIMIT, FILE_NAME_LENGTH_LIMIT, MS_DOS_DIRECTORY_ATTRIBUTE, ZIP64_FIELD_LIMIT,
+};
+
+const ZIP64_RECORD_COUNT_LIMIT: u64 = 0xFFFF;
+const UNIX_REGULAR_FILE_TYPE: u32 = 0o100000;
+const UNIX_DIRECTORY_TYPE: u32 = 0o040000;
+const UNIX_SYMLINK_TYPE: u32 = 0o120000;
+const UNIX_PERMISSION_BITS: u32 = 0o7777;
+const DEFAULT_UNIX_FILE_PERMISSIONS: u32 = 0o644;
+const DEFAULT_UNIX_DIRECTORY_PERMISSIONS: u32 = 0o755;
+const DEFAULT_UNIX_SYMLINK_PERMISSIONS: u32 = 0o777;
+const RESERVED_EXTRA_FIELD_IDS: [u16; 5] = [
+    ZIP64_EXTRA_FIELD_ID,
+    NTFS_EXTRA_FIELD_ID,
+    EXTENDED_TIMESTAMP_EXTRA_FIELD_ID,
+    UNICODE_PATH_EXTRA_FIELD_ID,
+    UNIX_OWNER_EXTRA_FIELD_ID,
+];
+
+#[derive(Default)]
+pub struct CentralDirectorySummary {
+    pub number_of_records: u64,
+    pub size: u64,
+    pub start_offset: u64,
+}
 
 pub struct ZipBlobFactoryAdapter {
     pub(crate) crc_calculator: Box<dyn CrcCalculator>,
     pub(crate) date_time_retriever: Box<dyn DosDateTimeCalculator>,
+    pub(crate) compressor: Box<dyn Compressor>,
+    pub(crate) options: ZipOptions,
 }
 
 impl ZipBlobFactory for ZipBlobFactoryAdapter {
-    fn create_zip_binary(&self, directory_mapping: HashMap<String, Vec<u8>>) -> Box<[u8]> {
-        let zip_file_entries = self.create_zip_file_entries(directory_mapping);
+    fn create_zip_binary(&self, directory_mapping: Vec<(String, FileDescriptor)>) -> Result<Box<[u8]>, ZipError> {
+        self.validate_archive_comment()?;
+
+        self.create_zip_blob(
+            Vec::new(),
+            Vec::new(),
+            &CentralDirectorySummary::default(),
+            &mut HashSet::new(),
+            directory_mapping,
+            self.options.comment.as_bytes(),
+        )
+    }
+
+    //The local entries of the archive are kept byte-for-byte, and the new entries overwrite its central directory, which
+    //is written again after them along with the new headers. The archive comment is kept unless a new one is given
+    fn append_zip_binary(&self, zip_archive: &ZipArchive, directory_mapping: Vec<(String, FileDescriptor)>) -> Result<Box<[u8]>, ZipError> {
+        validate_appended_file_names(zip_archive, &directory_mapping)?;
+
+        let zip_binary = zip_archive.get_data();
+        let central_directory_summary = zip_archive.get_central_directory_summary();
+
+        let central_directory_start = usize::try_from(central_directory_summary.start_offset).map_err(|_| ZipError::InvalidCentralDirectory)?;
+        let central_directory_end = usize::try_from(central_directory_summary.size).ok()
+            .and_then(|size| central_directory_start.checked_add(size))
+            .filter(|&central_directory_end| central_directory_end <= zip_binary.len())
+            .ok_or(ZipError::InvalidCentralDirectory)?;
+
+        let mut directory_names: HashSet<String> = zip_archive.get_entries().iter()
+            .filter(|zip_archive_entry| zip_archive_entry.is_directory())
+            .map(|zip_archive_entry| get_directory_name(&zip_archive_entry.file_name))
+            .collect();
+
+        let archive_comment = match self.options.comment.is_empty() {
+            true => zip_archive.get_raw_comment(),
+            false => {
+                self.validate_archive_comment()?;
+                self.options.comment.as_bytes()
+            }
+        };
 
+        self.create_zip_blob(
+            zip_binary[..central_directory_start].to_vec(),
+            zip_binary[central_directory_start..central_directory_end].to_vec(),
+            central_directory_summary,
+            &mut directory_names,
+            directory_mapping,
+            archive_comment,
+        )
+    }
+
+    //Entries without a new name are left out, and the others are copied without being decompressed, with only their names
+    //and offsets patched. The archive comment is copied as it is stored
+    fn rewrite_zip_binary(&self, zip_archive: &ZipArchive, get_new_file_name: &dyn Fn(&ZipArchiveEntry) -> Option<String>) -> Result<Box<[u8]>, ZipError> {
         let mut zip_blob: Vec<u8> = Vec::new();
         let mut central_directory_records: Vec<u8> = Vec::new();
-        let mut end_of_central_directory_record = self.get_end_of_central_directory_record(&zip_file_entries);
+        let mut number_of_records: u64 = 0;
+
+        for raw_zip_archive_entry in zip_archive.get_raw_entries()? {
+            let file_name = match get_new_file_name(raw_zip_archive_entry.zip_archive_entry) {
+                Some(file_name) => file_name,
+                None => continue,
+            };
+
+            if file_name.len() > FILE_NAME_LENGTH_LIMIT {
+                return Err(ZipError::FileNameTooLong { file_name });
+            }
+
+            let unicode_path_extra_field = self.get_unicode_path_extra_field(&file_name);
+            let entry_offset = zip_blob.len() as u64;
+
+            let mut local_file_record = raw_zip_archive_entry.get_local_file_record(&file_name, unicode_path_extra_field.as_ref())?;
+            let mut central_directory_header = raw_zip_archive_entry.get_central_directory_header(&file_name, unicode_path_extra_field.as_ref(), entry_offset)?;
+
+            zip_blob.append(&mut local_file_record);
+            central_directory_records.append(&mut central_directory_header);
+            number_of_records += 1;
+        }
+
+        let central_directory_summary = CentralDirectorySummary {
+            number_of_records,
+            size: central_directory_records.len() as u64,
+            start_offset: zip_blob.len() as u64,
+        };
+
+        let mut end_of_central_directory_records = self.get_end_of_central_directory_records(&central_directory_summary, zip_archive.get_raw_comment());
+
+        zip_blob.append(&mut central_directory_records);
+        zip_blob.append(&mut end_of_central_directory_records);
+
+        Ok(zip_blob.into_boxed_slice())
+    }
+}
+
+impl ZipBlobFactoryAdapter {
+    fn create_zip_blob(
+        &self,
+        mut zip_blob: Vec<u8>,
+        mut central_directory_records: Vec<u8>,
+        existing_central_directory_summary: &CentralDirectorySummary,
+        directory_names: &mut HashSet<String>,
+        directory_mapping: Vec<(String, FileDescriptor)>,
+        archive_comment: &[u8],
+    ) -> Result<Box<[u8]>, ZipError> {
+        let zip_file_entries = self.create_zip_file_entries(directory_mapping, existing_central_directory_summary, directory_names)?;
+
+        let central_directory_summary = self.get_central_directory_summary(existing_central_directory_summary, &zip_file_entries);
+        let mut end_of_central_directory_record = self.get_end_of_central_directory_records(&central_directory_summary, archive_comment);
 
         for file_entry in zip_file_entries {
             let mut local_file_header = file_entry.get_local_file_header();
             let mut central_directory_header = file_entry.get_central_directory_header();
+            let mut data_descriptor = file_entry.get_data_descriptor();
             let mut file_body = file_entry.body;
             zip_blob.append(&mut local_file_header);
             zip_blob.append(&mut file_body);
+            zip_blob.append(&mut data_descriptor);
             central_directory_records.append(&mut central_directory_header);
         }
 
         zip_blob.append(&mut central_directory_records);
         zip_blob.append(&mut end_of_central_directory_record);
 
-        zip_blob.into_boxed_slice()
+        Ok(zip_blob.into_boxed_slice())
     }
-}
 
-impl ZipBlobFactoryAdapter {
-    fn create_zip_file_entries(&self, directory_mapping: HashMap<String, Vec<u8>>) -> Vec<ZipFileEntry> {
+    //Offsets continue from the start of the existing central directory, which is where the first new entry is written
+    fn create_zip_file_entries(
+        &self,
+        directory_mapping: Vec<(String, FileDescriptor)>,
+        existing_central_directory_summary: &CentralDirectorySummary,
+        directory_names: &mut HashSet<String>,
+    ) -> Result<Vec<ZipFileEntry>, ZipError> {
+        let directory_mapping = self.get_directory_mapping_with_directories(directory_mapping, directory_names);
+
         let mut zip_file_entries: Vec<ZipFileEntry> = Vec::new();
 
-        let mut file_header_offset: u32 = 0;
+        let mut file_header_offset: u64 = existing_central_directory_summary.start_offset;
 
-        for (file_name, file_body) in directory_mapping.into_iter() {
-            let zip_entry = self.create_zip_file_entry(file_name, file_body, file_header_offset);
-            file_header_offset += self.get_zip_file_size(&zip_entry);
+        for (file_name, file_descriptor) in directory_mapping.into_iter() {
+            let zip_entry = self.create_validated_zip_file_entry(file_name, file_descriptor, file_header_offset)?;
+
+            file_header_offset = file_header_offset
+                .checked_add(self.get_zip_file_size(&zip_entry))
+                .ok_or(ZipError::OffsetOverflow)?;
             zip_file_entries.push(zip_entry);
         }
 
-        zip_file_entries
+        self.get_central_directory_size(&zip_file_entries)
+            .checked_add(existing_central_directory_summary.size)
+            .and_then(|central_directory_size| central_directory_size.checked_add(file_header_offset))
+            .ok_or(ZipError::OffsetOverflow)?;
+
+        Ok(zip_file_entries)
+    }
+
+    pub(crate) fn create_validated_zip_file_entry(
+        &self,
+        file_name: String,
+        file_descriptor: FileDescriptor,
+        header_offset: u64,
+    ) -> Result<ZipFileEntry, ZipError> {
+        self.validate_file(&file_name, &file_descriptor)?;
+
+        let zip_entry = self.create_zip_file_entry(file_name, file_descriptor, header_offset);
+        self.validate_zip_file_entry(&zip_entry)?;
+
+        Ok(zip_entry)
+    }
+
+    fn get_directory_mapping_with_directories(
+        &self,
+        directory_mapping: Vec<(String, FileDescriptor)>,
+        directory_names: &mut HashSet<String>,
+    ) -> Vec<(String, FileDescriptor)> {
+        let mut directory_mapping_with_directories: Vec<(String, FileDescriptor)> = Vec::new();
+
+        for (file_name, file_descriptor) in directory_mapping.into_iter() {
+            let mut entries = self.get_entries_with_directories(file_name, file_descriptor, directory_names);
+            directory_mapping_with_directories.append(&mut entries);
+        }
+
+        directory_mapping_with_directories
+    }
+
+    //Directory names that were already added are skipped, so each directory is only written once
+    pub(crate) fn get_entries_with_directories(
+        &self,
+        file_name: String,
+        file_descriptor: FileDescriptor,
+        directory_names: &mut HashSet<String>,
+    ) -> Vec<(String, FileDescriptor)> {
+        let mut entries: Vec<(String, FileDescriptor)> = Vec::new();
+
+        if file_descriptor.entry_type == EntryType::Directory && !self.options.directory_entries {
+            return entries;
+        }
+
+        if self.options.directory_entries {
+            for parent_directory_name in get_parent_directory_names(&file_name) {
+                if directory_names.insert(parent_directory_name.clone()) {
+                    entries.push((parent_directory_name, FileDescriptor {
+                        entry_type: EntryType::Directory,
+                        ..FileDescriptor::default()
+                    }));
+                }
+            }
+        }
+
+        if file_descriptor.entry_type == EntryType::Directory {
+            let directory_name = get_directory_name(&file_name);
+
+            if directory_names.insert(directory_name.clone()) {
+                entries.push((directory_name, file_descriptor));
+            }
+        } else {
+            entries.push((file_name, file_descriptor));
+        }
+
+        entries
+    }
+
+    //Readers find the end of central directory record by searching backwards for its signature, which the comment follows
+    pub(crate) fn validate_archive_comment(&self) -> Result<(), ZipError> {
+        let comment = self.options.comment.as_bytes();
+
+        if comment.len() > COMMENT_LENGTH_LIMIT {
+            return Err(ZipError::ArchiveCommentTooLong);
+        }
+
+        let signature = END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes();
+
+        if comment.windows(signature.len()).any(|window| window == signature) {
+            return Err(ZipError::ArchiveCommentContainsSignature);
+        }
+
+        Ok(())
+    }
+
+    fn validate_file(&self, file_name: &str, file_descriptor: &FileDescriptor) -> Result<(), ZipError> {
+        if file_name.len() > FILE_NAME_LENGTH_LIMIT {
+            return Err(ZipError::FileNameTooLong { file_name: file_name.to_string() });
+        }
+
+        if file_descriptor.comment.as_ref().map_or(0, String::len) > COMMENT_LENGTH_LIMIT {
+            return Err(ZipError::FileCommentTooLong { file_name: file_name.to_string() });
+        }
+
+        //These fields are written by the factory itself, so a second copy could contradict the header sizes or times
+        let reserved_extra_field = file_descriptor.local_extra_fields.iter()
+            .chain(file_descriptor.central_directory_extra_fields.iter())
+            .find(|extra_field| RESERVED_EXTRA_FIELD_IDS.contains(&extra_field.header_id));
+
+        if let Some(reserved_extra_field) = reserved_extra_field {
+            return Err(ZipError::ReservedExtraField { file_name: file_name.to_string(), header_id: reserved_extra_field.header_id });
+        }
+
+        Ok(())
     }
 
-    fn create_zip_file_entry(&self, zip_file_name: String, file_body: Vec<u8>, header_offset: u32) -> ZipFileEntry {
+    fn validate_zip_file_entry(&self, zip_entry: &ZipFileEntry) -> Result<(), ZipError> {
+        if zip_entry.get_local_extra_field_size() > EXTRA_FIELD_LENGTH_LIMIT
+            || zip_entry.get_central_directory_extra_field_size() > EXTRA_FIELD_LENGTH_LIMIT {
+            return Err(ZipError::ExtraFieldTooLong { file_name: zip_entry.file_name.clone() });
+        }
+
+        Ok(())
+    }
+
+    fn create_zip_file_entry(&self, zip_file_name: String, mut file_descriptor: FileDescriptor, header_offset: u64) -> ZipFileEntry {
+        let file_body = std::mem::take(&mut file_descriptor.body);
         let file_crc = self.calculate_file_crc(&file_body);
+        let uncompressed_size = file_body.len() as u64;
+        let compression_method = self.get_compression_method(&zip_file_name, &file_descriptor);
+        let compression_level = self.get_compression_level(&file_descriptor);
+        let (compression_method, body) = self.compress_file_body(file_body, compression_method, compression_level);
 
         ZipFileEntry {
-            body: file_body,
+            compressed_size: body.len() as u64,
+            body,
+            uncompressed_size,
+            compression_method,
             crc: file_crc,
+            ..self.create_empty_zip_file_entry(zip_file_name, file_descriptor, header_offset)
+        }
+    }
+
+    //Streamed entries have their header written before any of the body is known, so they keep the chosen compression
+    //method without falling back to storing, and their crc and sizes are filled in once the body has been written
+    pub(crate) fn create_validated_streamed_zip_file_entry(
+        &self,
+        file_name: String,
+        file_descriptor: FileDescriptor,
+        header_offset: u64,
+    ) -> Result<(ZipFileEntry, u8), ZipError> {
+        self.validate_file(&file_name, &file_descriptor)?;
+
+        let compression_level = self.get_compression_level(&file_descriptor);
+        let zip_entry = self.create_empty_zip_file_entry(file_name, file_descriptor, header_offset);
+        self.validate_zip_file_entry(&zip_entry)?;
+
+        Ok((zip_entry, compression_level))
+    }
+
+    fn create_empty_zip_file_entry(&self, zip_file_name: String, file_descriptor: FileDescriptor, header_offset: u64) -> ZipFileEntry {
+        let compression_method = self.get_compression_method(&zip_file_name, &file_descriptor);
+        let file_times = self.get_file_times(&file_descriptor);
+        let unix_mode = self.get_unix_mode(&file_descriptor);
+        let unix_owner = self.get_unix_owner(&file_descriptor);
+        let local_extra_fields = self.get_local_extra_fields(&zip_file_name, &file_times, unix_owner, file_descriptor.local_extra_fields);
+        let central_directory_extra_fields = self.get_central_directory_extra_fields(
+            &zip_file_name,
+            &file_times,
+            unix_owner,
+            file_descriptor.central_directory_extra_fields,
+        );
+        let (dos_time, dos_date) = self.get_dos_time_and_date(file_descriptor.last_modified);
+
+        ZipFileEntry {
+            body: Vec::new(),
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression_method,
+            crc: 0,
             file_name: zip_file_name,
-            dos_time: self.date_time_retriever.get_current_dos_time(),
-            dos_date: self.date_time_retriever.get_current_dos_date(),
+            dos_time,
+            dos_date,
             entry_offset: header_offset,
+            force_zip64: self.options.force_zip64,
+            data_descriptor: self.options.data_descriptor,
+            data_descriptor_signature: self.options.data_descriptor_signature,
+            local_extra_fields,
+            central_directory_extra_fields,
+            external_file_attributes: self.get_external_file_attributes(&file_descriptor.entry_type, unix_mode),
+            version_made_by: get_version_made_by(unix_mode.is_some()),
+            comment: file_descriptor.comment.unwrap_or_default(),
+        }
+    }
+
+    fn get_dos_time_and_date(&self, last_modified: Option<i64>) -> (u16, u16) {
+        match last_modified {
+            Some(last_modified) => {
+                let date_time = self.date_time_retriever.get_date_time(last_modified);
+
+                (self.date_time_retriever.get_dos_time(&date_time), self.date_time_retriever.get_dos_date(&date_time))
+            }
+            None => (self.date_time_retriever.get_current_dos_time(), self.date_time_retriever.get_current_dos_date()),
+        }
+    }
+
+    fn get_file_times(&self, file_descriptor: &FileDescriptor) -> FileTimes {
+        FileTimes {
+            modified: file_descriptor.last_modified.unwrap_or_else(|| self.date_time_retriever.get_current_unix_time_millis()),
+            accessed: file_descriptor.last_accessed,
+            created: file_descriptor.created,
+        }
+    }
+
+    //Unix mode bits live in the high 16 bits, alongside the MS-DOS attributes in the low bits
+    fn get_external_file_attributes(&self, entry_type: &EntryType, unix_mode: Option<u32>) -> u32 {
+        let ms_dos_attributes = match entry_type {
+            EntryType::File | EntryType::Symlink => 0,
+            EntryType::Directory => MS_DOS_DIRECTORY_ATTRIBUTE,
+        };
+
+        (unix_mode.unwrap_or(0) << 16) | ms_dos_attributes
+    }
+
+    //Symbolic links can only be recognised through their Unix file type, so they always carry a mode
+    fn get_unix_mode(&self, file_descriptor: &FileDescriptor) -> Option<u32> {
+        if file_descriptor.unix_mode.is_none()
+            && !self.options.unix_permissions
+            && file_descriptor.entry_type != EntryType::Symlink {
+            return None;
+        }
+
+        let (file_type, default_permissions) = match file_descriptor.entry_type {
+            EntryType::File => (UNIX_REGULAR_FILE_TYPE, DEFAULT_UNIX_FILE_PERMISSIONS),
+            EntryType::Directory => (UNIX_DIRECTORY_TYPE, DEFAULT_UNIX_DIRECTORY_PERMISSIONS),
+            EntryType::Symlink => (UNIX_SYMLINK_TYPE, DEFAULT_UNIX_SYMLINK_PERMISSIONS),
+        };
+
+        let permissions = file_descriptor.unix_mode.unwrap_or(default_permissions) & UNIX_PERMISSION_BITS;
+
+        Some(file_type | permissions)
+    }
+
+    //Unset ids default to root, as the field always holds both
+    fn get_unix_owner(&self, file_descriptor: &FileDescriptor) -> Option<(u32, u32)> {
+        if file_descriptor.uid.is_none() && file_descriptor.gid.is_none() {
+            return None;
+        }
+
+        Some((file_descriptor.uid.unwrap_or(0), file_descriptor.gid.unwrap_or(0)))
+    }
+
+    //Custom extra fields follow the ones written by the factory
+    fn get_local_extra_fields(
+        &self,
+        file_name: &str,
+        file_times: &FileTimes,
+        unix_owner: Option<(u32, u32)>,
+        custom_extra_fields: Vec<ExtraField>,
+    ) -> Vec<ExtraField> {
+        let mut local_extra_fields: Vec<ExtraField> = Vec::new();
+
+        if let Some(unicode_path_extra_field) = self.get_unicode_path_extra_field(file_name) {
+            local_extra_fields.push(unicode_path_extra_field);
+        }
+
+        if self.options.extended_timestamp_extra_field {
+            local_extra_fields.push(create_local_extended_timestamp_extra_field(file_times));
+        }
+
+        if self.options.ntfs_extra_field {
+            local_extra_fields.push(create_ntfs_extra_field(file_times));
+        }
+
+        if let Some((uid, gid)) = unix_owner {
+            local_extra_fields.push(create_local_unix_owner_extra_field(uid, gid));
+        }
+
+        local_extra_fields.extend(custom_extra_fields);
+
+        local_extra_fields
+    }
+
+    fn get_central_directory_extra_fields(
+        &self,
+        file_name: &str,
+        file_times: &FileTimes,
+        unix_owner: Option<(u32, u32)>,
+        custom_extra_fields: Vec<ExtraField>,
+    ) -> Vec<ExtraField> {
+        let mut central_directory_extra_fields: Vec<ExtraField> = Vec::new();
+
+        if let Some(unicode_path_extra_field) = self.get_unicode_path_extra_field(file_name) {
+            central_directory_extra_fields.push(unicode_path_extra_field);
         }
+
+        if self.options.extended_timestamp_extra_field {
+            central_directory_extra_fields.push(create_central_directory_extended_timestamp_extra_field(file_times));
+        }
+
+        if self.options.ntfs_extra_field {
+            central_directory_extra_fields.push(create_ntfs_extra_field(file_times));
+        }
+
+        if unix_owner.is_some() {
+            central_directory_extra_fields.push(create_central_directory_unix_owner_extra_field());
+        }
+
+        central_directory_extra_fields.extend(custom_extra_fields);
+
+        central_directory_extra_fields
+    }
+
+    //Older tools ignore the language encoding flag, but can still recover the name from this field
+    fn get_unicode_path_extra_field(&self, file_name: &str) -> Option<ExtraField> {
+        if !self.options.unicode_path_extra_field || file_name.is_ascii() {
+            return None;
+        }
+
+        let file_name_crc = self.crc_calculator.calculate_crc32(file_name.as_bytes());
+
+        Some(create_unicode_path_extra_field(file_name, file_name_crc))
     }
 
     fn calculate_file_crc(&self, file_contents: &[u8]) -> u32 {
         self.crc_calculator.calculate_crc32(file_contents)
     }
 
-    fn get_zip_file_size(&self, zip_file: &ZipFileEntry) -> u32 {
-        let local_file_header_size = zip_file.get_local_file_header_size() as u32;
-        let body_size = zip_file.body.len() as u32;
+    fn get_compression_method(&self, file_name: &str, file_descriptor: &FileDescriptor) -> CompressionMethod {
+        if file_descriptor.entry_type != EntryType::File {
+            return CompressionMethod::Stored;
+        }
+
+        if let Some(compression_method) = file_descriptor.compression_method {
+            return compression_method;
+        }
+
+        if self.is_known_incompressible(file_name, file_descriptor) {
+            return CompressionMethod::Stored;
+        }
+
+        self.options.compression_method
+    }
+
+    fn is_known_incompressible(&self, file_name: &str, file_descriptor: &FileDescriptor) -> bool {
+        let file_extension = get_file_extension(file_name);
+        let mime_type = file_descriptor.mime_type.as_deref().map(str::to_lowercase);
+
+        self.options.skip_compression.iter().any(|pattern| {
+            let pattern = pattern.to_lowercase();
+
+            if pattern.contains('/') {
+                mime_type.as_deref().is_some_and(|mime_type| is_mime_type_match(&pattern, mime_type))
+            } else {
+                file_extension.as_deref() == Some(pattern.trim_start_matches('.'))
+            }
+        })
+    }
+
+    fn get_compression_level(&self, file_descriptor: &FileDescriptor) -> u8 {
+        file_descriptor.compression_level.unwrap_or(self.options.compression_level)
+    }
+
+    fn compress_file_body(&self, file_body: Vec<u8>, compression_method: CompressionMethod, compression_level: u8) -> (CompressionMethod, Vec<u8>) {
+        match compression_method {
+            CompressionMethod::Stored => (CompressionMethod::Stored, file_body),
+            CompressionMethod::Deflate => {
+                let compressed_body = self.compressor.compress(&file_body, compression_level);
+
+                //Already compressed data tends to grow under deflate, in which case storing it is smaller
+                if compressed_body.len() < file_body.len() {
+                    (CompressionMethod::Deflate, compressed_body)
+                } else {
+                    (CompressionMethod::Stored, file_body)
+                }
+            }
+        }
+    }
+
+    pub(crate) fn get_zip_file_size(&self, zip_file: &ZipFileEntry) -> u64 {
+        let local_file_header_size = zip_file.get_local_file_header_size() as u64;
+        let body_size = zip_file.body.len() as u64;
+        let data_descriptor_size = zip_file.get_data_descriptor_size() as u64;
+
+        local_file_header_size + body_size + data_descriptor_size
+    }
+
+    pub(crate) fn get_end_of_central_directory_records(&self, central_directory_summary: &CentralDirectorySummary, archive_comment: &[u8]) -> Vec<u8> {
+        let mut end_of_central_directory_records: Vec<u8> = Vec::new();
+
+        if self.requires_zip64_end_of_central_directory(central_directory_summary) {
+            let mut zip64_end_of_central_directory_record = self.get_zip64_end_of_central_directory_record(central_directory_summary);
+            let mut zip64_end_of_central_directory_locator = self.get_zip64_end_of_central_directory_locator(central_directory_summary);
+
+            end_of_central_directory_records.append(&mut zip64_end_of_central_directory_record);
+            end_of_central_directory_records.append(&mut zip64_end_of_central_directory_locator);
+        }
+
+        let mut end_of_central_directory_record = self.get_end_of_central_directory_record(central_directory_summary, archive_comment);
+
+        end_of_central_directory_records.append(&mut end_of_central_directory_record);
+
+        end_of_central_directory_records
+    }
+
+    fn requires_zip64_end_of_central_directory(&self, central_directory_summary: &CentralDirectorySummary) -> bool {
+        self.options.force_zip64
+            || central_directory_summary.number_of_records >= ZIP64_RECORD_COUNT_LIMIT
+            || central_directory_summary.size >= ZIP64_FIELD_LIMIT
+            || central_directory_summary.start_offset >= ZIP64_FIELD_LIMIT
+    }
+
+    fn get_zip64_end_of_central_directory_record(&self, central_directory_summary: &CentralDirectorySummary) -> Vec<u8> {
+        let mut zip64_end_of_central_directory_record: Vec<u8> = vec![
+            0x50, 0x4B, 0x06, 0x06,                             //zip64 end of central directory signature
+            0x2C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,     //size of the remaining record
+        ];
+
+        zip64_end_of_central_directory_record.extend_from_slice(&get_version_made_by(self.options.unix_permissions).to_le_bytes());
+        zip64_end_of_central_directory_record.extend_from_slice(&[
+            0x2D, 0x00,                                         //version needed to extract
+            0x00, 0x00, 0x00, 0x00,                             //number of this disk
+            0x00, 0x00, 0x00, 0x00,                             //disk where central directory starts
+        ]);
+
+        zip64_end_of_central_directory_record.extend_from_slice(&central_directory_summary.number_of_records.to_le_bytes());
+        zip64_end_of_central_directory_record.extend_from_slice(&central_directory_summary.number_of_records.to_le_bytes());
+        zip64_end_of_central_directory_record.extend_from_slice(&central_directory_summary.size.to_le_bytes());
+        zip64_end_of_central_directory_record.extend_from_slice(&central_directory_summary.start_offset.to_le_bytes());
+
+        zip64_end_of_central_directory_record
+    }
+
+    fn get_zip64_end_of_central_directory_locator(&self, central_directory_summary: &CentralDirectorySummary) -> Vec<u8> {
+        let mut zip64_end_of_central_directory_locator: Vec<u8> = vec![
+            0x50, 0x4B, 0x06, 0x07,     //zip64 end of central directory locator signature
+            0x00, 0x00, 0x00, 0x00,     //disk where zip64 end of central directory starts
+        ];
+
+        let zip64_end_of_central_directory_offset = central_directory_summary.start_offset + central_directory_summary.size;
 
-        local_file_header_size + body_size
+        zip64_end_of_central_directory_locator.extend_from_slice(&zip64_end_of_central_directory_offset.to_le_bytes());
+        zip64_end_of_central_directory_locator.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);     //total number of disks
+
+        zip64_end_of_central_directory_locator
     }
 
-    fn get_end_of_central_directory_record(&self, zip_file_entries: &[ZipFileEntry]) -> Vec<u8> {
-        let mut end_of_central_directory_record: Vec<u8> = Vec::with_capacity(22);
+    fn get_end_of_central_directory_record(&self, central_directory_summary: &CentralDirectorySummary, archive_comment: &[u8]) -> Vec<u8> {
+        let mut end_of_central_directory_record: Vec<u8> = Vec::with_capacity(22 + archive_comment.len());
 
-        let mut disk_info_section: Vec<u8> = vec![
-            0x50, 0x4B, 0x05, 0x06,     //end of central directory signature
+        let mut disk_info_section: Vec<u8> = Vec::from(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        disk_info_section.extend_from_slice(&[
             0x00, 0x00,                 //number of this disk
             0x00, 0x00,                 //disk where central directory starts
-        ];
+        ]);
 
-        let mut total_number_of_central_directory_records = self.get_number_of_central_directory_records_section(zip_file_entries);
-        let mut number_of_central_directory_records_on_disk = self.get_number_of_central_directory_records_section(zip_file_entries);
-        let mut size_of_central_directory_section = self.get_size_of_central_directory_section(zip_file_entries);
-        let mut central_directory_start_section = self.get_central_directory_start_offset_section(zip_file_entries);
+        let mut total_number_of_central_directory_records = self.get_number_of_central_directory_records_section(central_directory_summary);
+        let mut number_of_central_directory_records_on_disk = self.get_number_of_central_directory_records_section(central_directory_summary);
+        let mut size_of_central_directory_section = self.get_size_of_central_directory_section(central_directory_summary);
+        let mut central_directory_start_section = self.get_central_directory_start_offset_section(central_directory_summary);
 
-        let mut comment_length: Vec<u8> = vec![0x00, 0x00];
+        let mut comment_length = Vec::from((archive_comment.len() as u16).to_le_bytes());
+        let mut comment = Vec::from(archive_comment);
 
         end_of_central_directory_record.append(&mut disk_info_section);
         end_of_central_directory_record.append(&mut total_number_of_central_directory_records);
@@ -95,510 +663,2195 @@ impl ZipBlobFactoryAdapter {
         end_of_central_directory_record.append(&mut size_of_central_directory_section);
         end_of_central_directory_record.append(&mut central_directory_start_section);
         end_of_central_directory_record.append(&mut comment_length);
+        end_of_central_directory_record.append(&mut comment);
 
         end_of_central_directory_record
     }
 
-    fn get_number_of_central_directory_records_section(&self, zip_file_entries: &[ZipFileEntry]) -> Vec<u8> {
-        let number_of_central_directory_records = zip_file_entries.len() as u16;
+    fn get_number_of_central_directory_records_section(&self, central_directory_summary: &CentralDirectorySummary) -> Vec<u8> {
+        let number_of_central_directory_records = central_directory_summary.number_of_records.min(ZIP64_RECORD_COUNT_LIMIT) as u16;
 
         Vec::from(number_of_central_directory_records.to_le_bytes())
     }
 
-    fn get_size_of_central_directory_section(&self, zip_file_entries: &[ZipFileEntry]) -> Vec<u8> {
-        let mut central_directory_size: u32 = 0;
+    fn get_size_of_central_directory_section(&self, central_directory_summary: &CentralDirectorySummary) -> Vec<u8> {
+        let central_directory_size = central_directory_summary.size.min(ZIP64_FIELD_LIMIT) as u32;
+
+        Vec::from(central_directory_size.to_le_bytes())
+    }
+
+    fn get_central_directory_start_offset_section(&self, central_directory_summary: &CentralDirectorySummary) -> Vec<u8> {
+        let start_offset = central_directory_summary.start_offset.min(ZIP64_FIELD_LIMIT) as u32;
+
+        Vec::from(start_offset.to_le_bytes())
+    }
+
+    fn get_central_directory_summary(
+        &self,
+        existing_central_directory_summary: &CentralDirectorySummary,
+        zip_file_entries: &[ZipFileEntry],
+    ) -> CentralDirectorySummary {
+        CentralDirectorySummary {
+            number_of_records: existing_central_directory_summary.number_of_records + zip_file_entries.len() as u64,
+            size: existing_central_directory_summary.size + self.get_central_directory_size(zip_file_entries),
+            start_offset: existing_central_directory_summary.start_offset + self.get_central_directory_start_offset(zip_file_entries),
+        }
+    }
+
+    fn get_central_directory_size(&self, zip_file_entries: &[ZipFileEntry]) -> u64 {
+        let mut central_directory_size: u64 = 0;
 
         for zip_file_entry in zip_file_entries {
-            central_directory_size += zip_file_entry.get_central_directory_header_size() as u32;
+            central_directory_size += zip_file_entry.get_central_directory_header_size() as u64;
         }
 
-        Vec::from(central_directory_size.to_le_bytes())
+        central_directory_size
     }
 
-    fn get_central_directory_start_offset_section(&self, zip_file_entries: &[ZipFileEntry]) -> Vec<u8> {
-        let mut start_offset: u32 = 0;
+    fn get_central_directory_start_offset(&self, zip_file_entries: &[ZipFileEntry]) -> u64 {
+        let mut start_offset: u64 = 0;
 
         for zip_file_entry in zip_file_entries {
-            start_offset += zip_file_entry.get_local_file_header_size() as u32;
-            start_offset += zip_file_entry.body.len() as u32;
+            start_offset += self.get_zip_file_size(zip_file_entry);
         }
 
-        Vec::from(start_offset.to_le_bytes())
+        start_offset
+    }
+}
+
+//Entries with a Unix mode are marked as made on Unix so that extractors read it from the external attributes, and the zip64
+//end of central directory record is marked the same way when every entry gets one
+fn get_version_made_by(has_unix_mode: bool) -> u16 {
+    match has_unix_mode {
+        true => VERSION_MADE_BY_UNIX,
+        false => VERSION_MADE_BY_MS_DOS,
+    }
+}
+
+//Folders that already exist are skipped when the new entries are written, so only a new file or symlink can take the
+//name of an existing entry, including folders implied by the paths of its files
+fn validate_appended_file_names(zip_archive: &ZipArchive, directory_mapping: &[(String, FileDescriptor)]) -> Result<(), ZipError> {
+    let mut file_names: HashSet<String> = HashSet::new();
+    let mut directory_names: HashSet<String> = HashSet::new();
+
+    for zip_archive_entry in zip_archive.get_entries() {
+        directory_names.extend(get_parent_directory_names(&zip_archive_entry.file_name));
+
+        match zip_archive_entry.is_directory() {
+            true => directory_names.insert(get_directory_name(&zip_archive_entry.file_name)),
+            false => file_names.insert(zip_archive_entry.file_name.clone()),
+        };
+    }
+
+    for (file_name, file_descriptor) in directory_mapping {
+        let is_conflicting = match file_descriptor.entry_type {
+            EntryType::Directory => file_names.contains(file_name.trim_end_matches('/')),
+            _ => file_names.contains(file_name) || directory_names.contains(&get_directory_name(file_name)),
+        };
+
+        if is_conflicting {
+            return Err(ZipError::ConflictingEntryName { file_name: file_name.clone() });
+        }
+    }
+
+    Ok(())
+}
+
+fn get_directory_name(file_name: &str) -> String {
+    if file_name.ends_with('/') {
+        return file_name.to_string();
+    }
+
+    format!("{}/", file_name)
+}
+
+fn get_parent_directory_names(file_name: &str) -> Vec<String> {
+    file_name.match_indices('/')
+        .map(|(separator_index, _)| String::from(&file_name[..=separator_index]))
+        .filter(|parent_directory_name| parent_directory_name != file_name)
+        .collect()
+}
+
+fn get_file_extension(file_name: &str) -> Option<String> {
+    let base_name = file_name.rsplit('/').next().unwrap_or(file_name);
+    let (_, extension) = base_name.rsplit_once('.')?;
+
+    Some(extension.to_lowercase())
+}
+
+fn is_mime_type_match(pattern: &str, mime_type: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some(media_type) => mime_type.split('/').next() == Some(media_type),
+        None => pattern == mime_type,
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(test)]
+#[allow(clippy::vec_init_then_push)]
+mod tests {
+    use super::*;
+    use crate::date_time_converter::dos_date_time_calculator_adapter::DosDateTimeCalculatorAdapter;
+    use crate::date_time_converter::SystemTime;
+
+    struct FakeCrcCalculator {}
+
+    impl CrcCalculator for FakeCrcCalculator {
+        fn calculate_crc32(&self, _data: &[u8]) -> u32 {
+            0x11223344
+        }
+    }
+
+    struct FakeCompressor {}
+
+    impl Compressor for FakeCompressor {
+        fn compress(&self, data: &[u8], _level: u8) -> Vec<u8> {
+            data[0..data.len() / 2].to_vec()
+        }
+    }
+
+    struct FakeExpandingCompressor {}
+
+    impl Compressor for FakeExpandingCompressor {
+        fn compress(&self, data: &[u8], _level: u8) -> Vec<u8> {
+            [data, &[0x00]].concat()
+        }
+    }
+
+    struct FakeLevelCompressor {}
+
+    impl Compressor for FakeLevelCompressor {
+        fn compress(&self, _data: &[u8], level: u8) -> Vec<u8> {
+            vec![level]
+        }
+    }
+
+    struct FakeDosDateTimeRetriever {}
+
+    impl DosDateTimeCalculator for FakeDosDateTimeRetriever {
+        fn get_current_dos_time(&self) -> u16 {
+            0x1133
+        }
+
+        fn get_current_dos_date(&self) -> u16 {
+            0x9988
+        }
+
+        fn get_current_unix_time_millis(&self) -> i64 {
+            1654688302000
+        }
+
+        fn get_date_time(&self, unix_time_millis: i64) -> SystemTime {
+            SystemTime::from_unix_time_millis(unix_time_millis)
+        }
+
+        fn get_dos_time(&self, date_time: &SystemTime) -> u16 {
+            date_time.seconds
+        }
+
+        fn get_dos_date(&self, date_time: &SystemTime) -> u16 {
+            date_time.day
+        }
+    }
+
+    #[test]
+    fn zip_file_size_should_be_the_sum_of_body_header_lengths() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let fake_file_entry = ZipFileEntry {
+            body: vec![0; 293],
+            compressed_size: 293,
+            uncompressed_size: 293,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("blab"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        assert_eq!(327, blob_factory_adapter.get_zip_file_size(&fake_file_entry));
+    }
+
+    #[test]
+    fn zip_file_size_includes_data_descriptor() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let fake_file_entry = ZipFileEntry {
+            body: vec![0; 293],
+            compressed_size: 293,
+            uncompressed_size: 293,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("blab"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: true,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        assert_eq!(343, blob_factory_adapter.get_zip_file_size(&fake_file_entry));
+    }
+
+    #[test]
+    fn calculate_file_crc_should_delegate_to_crc_calculator() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let fake_file_body: Vec<u8> = vec![0; 33];
+
+        assert_eq!(0x11223344, blob_factory_adapter.calculate_file_crc(&fake_file_body));
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_create_zip_entry_with_given_data() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let file_name = String::from("BugCat");
+        let file_body: Vec<u8> = vec![0; 33];
+        let header_offset: u64 = 98;
+
+        let file_descriptor = FileDescriptor { body: file_body.clone(), ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(file_name.clone(), file_descriptor, header_offset);
+
+        assert_eq!(file_name, created_file_entry.file_name);
+        assert_eq!(file_body, created_file_entry.body);
+        assert_eq!(header_offset, created_file_entry.entry_offset);
+        assert_eq!(0x11223344, created_file_entry.crc);
+        assert_eq!(0x1133, created_file_entry.dos_time);
+        assert_eq!(0x9988, created_file_entry.dos_date);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_body_without_compression() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("BugCat"), file_descriptor, 0);
+
+        assert_eq!(vec![7; 40], created_file_entry.body);
+        assert_eq!(40, created_file_entry.uncompressed_size);
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_compress_body_with_deflate() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Deflate, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("BugCat"), file_descriptor, 0);
+
+        assert_eq!(vec![7; 20], created_file_entry.body);
+        assert_eq!(40, created_file_entry.uncompressed_size);
+        assert_eq!(CompressionMethod::Deflate, created_file_entry.compression_method);
+        assert_eq!(0x11223344, created_file_entry.crc);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_empty_body_with_deflate() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Deflate, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Empty.txt"), file_descriptor, 0);
+
+        assert_eq!(Vec::<u8>::new(), created_file_entry.body);
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_prefer_file_compression_method_over_archive_default() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Deflate, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            compression_method: Some(CompressionMethod::Stored),
+            ..FileDescriptor::default()
+        };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.jpeg"), file_descriptor, 0);
+
+        assert_eq!(vec![7; 40], created_file_entry.body);
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_use_archive_compression_level_by_default() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeLevelCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Deflate, compression_level: 3, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.json"), file_descriptor, 0);
+
+        assert_eq!(vec![3], created_file_entry.body);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_prefer_file_compression_level_over_archive_default() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeLevelCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, compression_level: 3, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            compression_method: Some(CompressionMethod::Deflate),
+            compression_level: Some(9),
+            ..FileDescriptor::default()
+        };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.json"), file_descriptor, 0);
+
+        assert_eq!(vec![9], created_file_entry.body);
+        assert_eq!(CompressionMethod::Deflate, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_body_when_compression_does_not_shrink_it() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeExpandingCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Deflate, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.mp4"), file_descriptor, 0);
+
+        assert_eq!(vec![7; 40], created_file_entry.body);
+        assert_eq!(40, created_file_entry.uncompressed_size);
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_body_with_skipped_extension() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions {
+                compression_method: CompressionMethod::Deflate,
+                skip_compression: vec![String::from(".png"), String::from("zip")],
+                ..ZipOptions::default()
+            },
+        };
+
+        let png_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+        let zip_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let png_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Folder/Capoo.PNG"), png_descriptor, 0);
+        let zip_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Nested.zip"), zip_descriptor, 0);
+
+        assert_eq!(CompressionMethod::Stored, png_file_entry.compression_method);
+        assert_eq!(CompressionMethod::Stored, zip_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_body_with_skipped_mime_type() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions {
+                compression_method: CompressionMethod::Deflate,
+                skip_compression: vec![String::from("video/*"), String::from("image/png")],
+                ..ZipOptions::default()
+            },
+        };
+
+        let video_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            mime_type: Some(String::from("video/mp4")),
+            ..FileDescriptor::default()
+        };
+        let image_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            mime_type: Some(String::from("image/jpeg")),
+            ..FileDescriptor::default()
+        };
+
+        let video_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo"), video_descriptor, 0);
+        let image_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("FoamCat"), image_descriptor, 0);
+
+        assert_eq!(CompressionMethod::Stored, video_file_entry.compression_method);
+        assert_eq!(CompressionMethod::Deflate, image_file_entry.compression_method);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_prefer_file_compression_method_over_skip_list() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions {
+                compression_method: CompressionMethod::Deflate,
+                skip_compression: vec![String::from("svg")],
+                ..ZipOptions::default()
+            },
+        };
+
+        let file_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            compression_method: Some(CompressionMethod::Deflate),
+            ..FileDescriptor::default()
+        };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.svg"), file_descriptor, 0);
+
+        assert_eq!(CompressionMethod::Deflate, created_file_entry.compression_method);
+    }
+
+    #[test]
+    fn creating_multiple_entries_from_directory_mapping_with_sequential_header_offsets() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, directory_entries: false, ..ZipOptions::default() },
+        };
+
+        let given_directory_mapping: Vec<(String, FileDescriptor)> = vec![
+            (String::from("BugCat.txt"), FileDescriptor { body: vec![2; 16], ..FileDescriptor::default() }),
+            (String::from("MyFolder/FoamCat.txt"), FileDescriptor { body: vec![0; 11], ..FileDescriptor::default() }),
+        ];
+
+        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_directory_mapping, &CentralDirectorySummary::default(), &mut HashSet::new()).unwrap();
+
+        assert_eq!(vec![2; 16], created_file_entries[0].body);
+        assert_eq!(0, created_file_entries[0].entry_offset);
+
+        assert_eq!(vec![0; 11], created_file_entries[1].body);
+        assert_eq!(56, created_file_entries[1].entry_offset);
+    }
+
+    #[test]
+    fn number_of_central_directory_records_with_one_entry() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![],
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "a".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let number_of_central_directory_records = blob_factory_adapter.get_number_of_central_directory_records_section(&central_directory_summary);
+
+        assert_eq!([0x01, 0x00], &number_of_central_directory_records[0..2]);
+    }
+
+    #[test]
+    fn number_of_central_directory_records_with_many_entries() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        for _entry_number in 0..1000 {
+            zip_entries.push(ZipFileEntry {
+                body: vec![],
+                compressed_size: 0,
+                uncompressed_size: 0,
+                compression_method: CompressionMethod::Stored,
+                crc: 0,
+                file_name: "a".to_string(),
+                dos_time: 0,
+                dos_date: 0,
+                entry_offset: 0,
+                force_zip64: false,
+                data_descriptor: false,
+                data_descriptor_signature: false,
+                local_extra_fields: Vec::new(),
+                central_directory_extra_fields: Vec::new(),
+                external_file_attributes: 0,
+                version_made_by: 0x003F,
+                comment: String::new(),
+            });
+        }
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let number_of_central_directory_records = blob_factory_adapter.get_number_of_central_directory_records_section(&central_directory_summary);
+
+        assert_eq!([0xE8, 0x03], &number_of_central_directory_records[0..2]);
+    }
+
+    #[test]
+    fn central_directory_size_with_one_entry() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let central_directory_size = blob_factory_adapter.get_size_of_central_directory_section(&central_directory_summary);
+
+        assert_eq!([0x38, 0x00, 0x00, 0x00], &central_directory_size[0..4]);
+    }
+
+    #[test]
+    fn central_directory_size_with_many_entries() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        for _entry_number in 0..10 {
+            zip_entries.push(ZipFileEntry {
+                body: vec![0; 10],
+                compressed_size: 10,
+                uncompressed_size: 10,
+                compression_method: CompressionMethod::Stored,
+                crc: 0,
+                file_name: "BugCat.txt".to_string(),
+                dos_time: 0,
+                dos_date: 0,
+                entry_offset: 0,
+                force_zip64: false,
+                data_descriptor: false,
+                data_descriptor_signature: false,
+                local_extra_fields: Vec::new(),
+                central_directory_extra_fields: Vec::new(),
+                external_file_attributes: 0,
+                version_made_by: 0x003F,
+                comment: String::new(),
+            });
+        }
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let central_directory_size = blob_factory_adapter.get_size_of_central_directory_section(&central_directory_summary);
+
+        assert_eq!([0x30, 0x02, 0x00, 0x00], &central_directory_size[0..4]);
+    }
+
+    #[test]
+    fn central_directory_offset_with_one_entry() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let central_directory_offset = blob_factory_adapter.get_central_directory_start_offset_section(&central_directory_summary);
+
+        assert_eq!([0x32, 0x00, 0x00, 0x00], &central_directory_offset[0..4]);
+    }
+
+    #[test]
+    fn end_of_central_directory_signature() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &end_of_central_directory[0..4]);
+    }
+
+    #[test]
+    fn end_of_central_directory_number_of_disk() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x00, 0x00], &end_of_central_directory[4..6]);
+    }
+
+    #[test]
+    fn end_of_central_directory_disk_where_central_directory_starts() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x00, 0x00], &end_of_central_directory[6..8]);
+    }
+
+    #[test]
+    fn end_of_central_directory_number_of_records_on_disk() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x01, 0x00], &end_of_central_directory[8..10]);
+    }
+
+    #[test]
+    fn end_of_central_directory_total_number_of_records() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x01, 0x00], &end_of_central_directory[10..12]);
+    }
+
+    #[test]
+    fn end_of_central_directory_size_of_central_directory() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x38, 0x00, 0x00, 0x00], &end_of_central_directory[12..16]);
+    }
+
+    #[test]
+    fn end_of_central_directory_start_offset_of_central_directory() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x32, 0x00, 0x00, 0x00], &end_of_central_directory[16..20]);
+    }
+
+    #[test]
+    fn end_of_central_directory_start_comment_length() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        zip_entries.push(ZipFileEntry {
+            body: vec![0; 10],
+            compressed_size: 10,
+            uncompressed_size: 10,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: "BugCat.txt".to_string(),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        });
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, &[]);
+
+        assert_eq!([0x00, 0x00], &end_of_central_directory[20..22]);
+    }
+
+    #[test]
+    fn end_of_central_directory_with_archive_comment() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &[]);
+        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&central_directory_summary, b"Capoo");
+
+        assert_eq!(27, end_of_central_directory.len());
+        assert_eq!([0x05, 0x00], &end_of_central_directory[20..22]);
+        assert_eq!("Capoo".as_bytes(), &end_of_central_directory[22..27]);
+    }
+
+    #[test]
+    fn single_file_zip_blob_header_signatures() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let file_contents = String::from("Capoo is Hungry.");
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(file_contents.as_bytes()), ..FileDescriptor::default() }),
+        ];
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(input_map).unwrap();
+
+        assert_eq!(132, zip_blob.len());
+
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[55..59]);
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[110..114]);
+    }
+
+    #[test]
+    fn multiple_file_zip_blob_header_signatures() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, directory_entries: false, ..ZipOptions::default() },
+        };
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+            (String::from("Folder/Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ];
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(input_map).unwrap();
+
+        assert_eq!(237, zip_blob.len());
+
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[101..105]);
+
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[55..59]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[156..160]);
+
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[215..219]);
+    }
+
+    #[test]
+    fn appended_zip_blob_should_match_zip_blob_created_at_once() {
+        for options in [ZipOptions::default(), ZipOptions { force_zip64: true, data_descriptor: true, ..ZipOptions::default() }] {
+            let blob_factory_adapter = ZipBlobFactoryAdapter {
+                crc_calculator: Box::new(FakeCrcCalculator {}),
+                date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+                compressor: Box::new(FakeCompressor {}),
+                options,
+            };
+
+            let get_directory_mapping = || -> Vec<(String, FileDescriptor)> {
+                vec![
+                    (String::from("Folder/Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+                    (String::from("Folder/Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+                    (String::from("Other/Yow.txt"), FileDescriptor { body: vec![4; 40], ..FileDescriptor::default() }),
+                ]
+            };
+
+            let mut directory_mapping = get_directory_mapping();
+            let appended_directory_mapping = directory_mapping.split_off(1);
+
+            let zip_blob = blob_factory_adapter.create_zip_binary(directory_mapping).unwrap();
+            let appended_zip_blob = blob_factory_adapter.append_zip_binary(&ZipArchive::new(&zip_blob).unwrap(), appended_directory_mapping).unwrap();
+
+            assert_eq!(blob_factory_adapter.create_zip_binary(get_directory_mapping()).unwrap(), appended_zip_blob);
+        }
+    }
+
+    #[test]
+    fn appended_zip_blob_should_keep_existing_local_entries() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        let appended_zip_blob = blob_factory_adapter.append_zip_binary(&ZipArchive::new(&zip_blob).unwrap(), vec![
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        assert_eq!(223, appended_zip_blob.len());
+
+        assert_eq!(&zip_blob[..55], &appended_zip_blob[..55]);
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &appended_zip_blob[55..59]);
+        assert_eq!(&zip_blob[55..110], &appended_zip_blob[94..149]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &appended_zip_blob[149..153]);
+        assert_eq!([0x37, 0x00, 0x00, 0x00], &appended_zip_blob[191..195]);
+
+        assert_eq!([0x02, 0x00], &appended_zip_blob[211..213]);
+        assert_eq!([0x6B, 0x00, 0x00, 0x00], &appended_zip_blob[213..217]);
+        assert_eq!([0x5E, 0x00, 0x00, 0x00], &appended_zip_blob[217..221]);
+    }
+
+    #[test]
+    fn appending_entries_named_like_existing_entries() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("Folder/Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        let zip_archive = ZipArchive::new(&zip_blob).unwrap();
+
+        for file_name in ["Folder/Hello.txt", "Folder", "Hi.csv"] {
+            let zip_error = blob_factory_adapter.append_zip_binary(&zip_archive, vec![
+                (String::from(file_name), FileDescriptor { body: vec![4; 40], ..FileDescriptor::default() }),
+            ]).unwrap_err();
+
+            assert_eq!(ZipError::ConflictingEntryName { file_name: String::from(file_name) }, zip_error);
+        }
+
+        let zip_error = blob_factory_adapter.append_zip_binary(&zip_archive, vec![
+            (String::from("Hi.csv/"), FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }),
+        ]).unwrap_err();
+
+        assert_eq!(ZipError::ConflictingEntryName { file_name: String::from("Hi.csv/") }, zip_error);
+
+        blob_factory_adapter.append_zip_binary(&zip_archive, vec![
+            (String::from("Folder/"), FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }),
+            (String::from("Folder/Hi.csv"), FileDescriptor { body: vec![4; 40], ..FileDescriptor::default() }),
+        ]).unwrap();
+    }
+
+    //Comments are only checked when they are given, so an archive written elsewhere may hold bytes that are not UTF-8 or
+    //that look like a signature
+    fn set_archive_comment(zip_blob: &[u8], archive_comment: &[u8]) -> Vec<u8> {
+        let mut zip_blob = zip_blob[..zip_blob.len() - 2].to_vec();
+
+        zip_blob.extend_from_slice(&(archive_comment.len() as u16).to_le_bytes());
+        zip_blob.extend_from_slice(archive_comment);
+
+        zip_blob
+    }
+
+    #[test]
+    fn appended_zip_blob_should_keep_existing_archive_comment_bytes() {
+        let archive_comment = [0x43, 0x61, 0x70, 0x6F, 0x6F, 0x20, 0x80, 0xFF, 0x50, 0x4B, 0x05, 0x06];
+
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+        ]).unwrap();
+        let zip_blob = set_archive_comment(&zip_blob, &archive_comment);
+
+        let appended_zip_blob = blob_factory_adapter.append_zip_binary(&ZipArchive::new(&zip_blob).unwrap(), vec![
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        assert_eq!(archive_comment, appended_zip_blob[appended_zip_blob.len() - 12..]);
+        assert_eq!([0x0C, 0x00], appended_zip_blob[appended_zip_blob.len() - 14..appended_zip_blob.len() - 12]);
+
+        let blob_factory_adapter = ZipBlobFactoryAdapter { options: ZipOptions { comment: String::from("Capoo archive"), ..ZipOptions::default() }, ..blob_factory_adapter };
+
+        let appended_zip_blob = blob_factory_adapter.append_zip_binary(&ZipArchive::new(&zip_blob).unwrap(), vec![
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        assert_eq!(b"Capoo archive", &appended_zip_blob[appended_zip_blob.len() - 13..]);
+    }
+
+    #[test]
+    fn rewritten_zip_blob_should_keep_existing_archive_comment_bytes() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+        let zip_blob = set_archive_comment(&zip_blob, &[0x43, 0x61, 0x70, 0x6F, 0x6F, 0x20, 0x80, 0xFF, 0x50, 0x4B, 0x05, 0x06]);
+
+        let zip_archive = ZipArchive::new(&zip_blob).unwrap();
+        let rewritten_zip_blob = blob_factory_adapter.rewrite_zip_binary(&zip_archive, &|zip_archive_entry| Some(zip_archive_entry.file_name.clone())).unwrap();
+
+        assert_eq!(zip_blob.into_boxed_slice(), rewritten_zip_blob);
+    }
+
+    #[test]
+    fn rewritten_zip_blob_without_changes_should_be_identical() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { data_descriptor: true, comment: String::from("Capoo archive"), ..ZipOptions::default() },
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("Folder/Hello.txt"), FileDescriptor { body: vec![1; 40], comment: Some(String::from("Hungry")), ..FileDescriptor::default() }),
+            (String::from("Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        let zip_archive = ZipArchive::new(&zip_blob).unwrap();
+        let rewritten_zip_blob = blob_factory_adapter.rewrite_zip_binary(&zip_archive, &|zip_archive_entry| Some(zip_archive_entry.file_name.clone())).unwrap();
+
+        assert_eq!(zip_blob, rewritten_zip_blob);
+    }
+
+    #[test]
+    fn rewritten_zip_blob_with_deleted_and_renamed_entries() {
+        for options in [ZipOptions::default(), ZipOptions { unicode_path_extra_field: true, data_descriptor: true, ..ZipOptions::default() }] {
+            let blob_factory_adapter = ZipBlobFactoryAdapter {
+                crc_calculator: Box::new(FakeCrcCalculator {}),
+                date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+                compressor: Box::new(FakeCompressor {}),
+                options,
+            };
+
+            let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+                (String::from("Folder/Hello.txt"), FileDescriptor { body: vec![1; 40], ..FileDescriptor::default() }),
+                (String::from(".DS_Store"), FileDescriptor { body: vec![2; 10], ..FileDescriptor::default() }),
+                (String::from("Folder/Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+            ]).unwrap();
+
+            let zip_archive = ZipArchive::new(&zip_blob).unwrap();
+            let rewritten_zip_blob = blob_factory_adapter.rewrite_zip_binary(&zip_archive, &|zip_archive_entry| {
+                match zip_archive_entry.file_name.as_str() {
+                    ".DS_Store" => None,
+                    _ => zip_archive_entry.get_renamed_file_name("Folder", "猫"),
+                }
+            }).unwrap();
+
+            let expected_zip_blob = blob_factory_adapter.create_zip_binary(vec![
+                (String::from("猫/Hello.txt"), FileDescriptor { body: vec![1; 40], ..FileDescriptor::default() }),
+                (String::from("猫/Hi.csv"), FileDescriptor { body: Vec::from([1, 2, 3]), ..FileDescriptor::default() }),
+            ]).unwrap();
+
+            assert_eq!(expected_zip_blob, rewritten_zip_blob);
+        }
+    }
+
+    #[test]
+    fn rewritten_zip_blob_with_too_long_file_name() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![(String::from("Hi.csv"), FileDescriptor::default())]).unwrap();
+
+        let zip_archive = ZipArchive::new(&zip_blob).unwrap();
+        let zip_error = blob_factory_adapter.rewrite_zip_binary(&zip_archive, &|_| Some("a".repeat(0x10000))).unwrap_err();
+
+        assert_eq!(ZipError::FileNameTooLong { file_name: "a".repeat(0x10000) }, zip_error);
+    }
+
+    #[test]
+    fn number_of_central_directory_records_with_too_many_entries_for_zip32() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+
+        for _entry_number in 0..70000 {
+            zip_entries.push(ZipFileEntry {
+                body: vec![],
+                compressed_size: 0,
+                uncompressed_size: 0,
+                compression_method: CompressionMethod::Stored,
+                crc: 0,
+                file_name: "a".to_string(),
+                dos_time: 0,
+                dos_date: 0,
+                entry_offset: 0,
+                force_zip64: false,
+                data_descriptor: false,
+                data_descriptor_signature: false,
+                local_extra_fields: Vec::new(),
+                central_directory_extra_fields: Vec::new(),
+                external_file_attributes: 0,
+                version_made_by: 0x003F,
+                comment: String::new(),
+            });
+        }
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory_records = blob_factory_adapter.get_end_of_central_directory_records(&central_directory_summary, &[]);
+
+        assert_eq!(98, end_of_central_directory_records.len());
+        assert_eq!([0x50, 0x4B, 0x06, 0x06], &end_of_central_directory_records[0..4]);
+        assert_eq!([0x70, 0x11, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[24..32]);
+        assert_eq!([0x70, 0x11, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[32..40]);
+        assert_eq!([0x50, 0x4B, 0x06, 0x07], &end_of_central_directory_records[56..60]);
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &end_of_central_directory_records[76..80]);
+        assert_eq!([0xFF, 0xFF], &end_of_central_directory_records[84..86]);
+        assert_eq!([0xFF, 0xFF], &end_of_central_directory_records[86..88]);
+    }
+
+    #[test]
+    fn end_of_central_directory_records_without_zip64() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, ..ZipOptions::default() },
+        };
+
+        let zip_entries: Vec<ZipFileEntry> = vec![
+            ZipFileEntry {
+                body: vec![0; 10],
+                compressed_size: 10,
+                uncompressed_size: 10,
+                compression_method: CompressionMethod::Stored,
+                crc: 0,
+                file_name: "BugCat.txt".to_string(),
+                dos_time: 0,
+                dos_date: 0,
+                entry_offset: 0,
+                force_zip64: false,
+                data_descriptor: false,
+                data_descriptor_signature: false,
+                local_extra_fields: Vec::new(),
+                central_directory_extra_fields: Vec::new(),
+                external_file_attributes: 0,
+                version_made_by: 0x003F,
+                comment: String::new(),
+            }
+        ];
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory_records = blob_factory_adapter.get_end_of_central_directory_records(&central_directory_summary, &[]);
+
+        assert_eq!(22, end_of_central_directory_records.len());
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &end_of_central_directory_records[0..4]);
+    }
+
+    #[test]
+    fn forced_zip64_end_of_central_directory_records() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, force_zip64: true, ..ZipOptions::default() },
+        };
+
+        let zip_entries: Vec<ZipFileEntry> = vec![
+            ZipFileEntry {
+                body: vec![0; 10],
+                compressed_size: 10,
+                uncompressed_size: 10,
+                compression_method: CompressionMethod::Stored,
+                crc: 0,
+                file_name: "BugCat.txt".to_string(),
+                dos_time: 0,
+                dos_date: 0,
+                entry_offset: 0,
+                force_zip64: true,
+                data_descriptor: false,
+                data_descriptor_signature: false,
+                local_extra_fields: Vec::new(),
+                central_directory_extra_fields: Vec::new(),
+                external_file_attributes: 0,
+                version_made_by: 0x003F,
+                comment: String::new(),
+            }
+        ];
+
+        let central_directory_summary = blob_factory_adapter.get_central_directory_summary(&CentralDirectorySummary::default(), &zip_entries);
+        let end_of_central_directory_records = blob_factory_adapter.get_end_of_central_directory_records(&central_directory_summary, &[]);
+
+        assert_eq!(98, end_of_central_directory_records.len());
+
+        assert_eq!([0x50, 0x4B, 0x06, 0x06], &end_of_central_directory_records[0..4]);
+        assert_eq!([0x2C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[4..12]);
+        assert_eq!([0x3F, 0x00], &end_of_central_directory_records[12..14]);
+        assert_eq!([0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[24..32]);
+        assert_eq!([0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[32..40]);
+        assert_eq!([0x54, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[40..48]);
+        assert_eq!([0x46, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[48..56]);
+
+        assert_eq!([0x50, 0x4B, 0x06, 0x07], &end_of_central_directory_records[56..60]);
+        assert_eq!([0x9A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &end_of_central_directory_records[64..72]);
+        assert_eq!([0x01, 0x00, 0x00, 0x00], &end_of_central_directory_records[72..76]);
+
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &end_of_central_directory_records[76..80]);
+    }
+
+    #[test]
+    fn forced_zip64_end_of_central_directory_records_with_unix_permissions() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { force_zip64: true, unix_permissions: true, ..ZipOptions::default() },
+        };
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(vec![
+            (String::from("BugCat.txt"), FileDescriptor { body: vec![0; 10], ..FileDescriptor::default() }),
+        ]).unwrap();
+
+        let central_directory_start = zip_blob.windows(4).position(|signature| signature == [0x50, 0x4B, 0x01, 0x02]).unwrap();
+        let zip64_end_of_central_directory_start = zip_blob.len() - 98;
+
+        assert_eq!([0x3F, 0x03], &zip_blob[central_directory_start + 4..central_directory_start + 6]);
+        assert_eq!([0x50, 0x4B, 0x06, 0x06], &zip_blob[zip64_end_of_central_directory_start..zip64_end_of_central_directory_start + 4]);
+        assert_eq!([0x3F, 0x03], &zip_blob[zip64_end_of_central_directory_start + 12..zip64_end_of_central_directory_start + 14]);
+    }
+
+    #[test]
+    fn forced_zip64_zip_blob_header_signatures() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, force_zip64: true, ..ZipOptions::default() },
+        };
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Hello.txt"), FileDescriptor { body: Vec::from(String::from("Capoo is Hungry.").as_bytes()), ..FileDescriptor::default() }),
+        ];
+
+        let zip_blob = blob_factory_adapter.create_zip_binary(input_map).unwrap();
+
+        assert_eq!(256, zip_blob.len());
+
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[75..79]);
+        assert_eq!([0x50, 0x4B, 0x06, 0x06], &zip_blob[158..162]);
+        assert_eq!([0x50, 0x4B, 0x06, 0x07], &zip_blob[214..218]);
+        assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[234..238]);
+    }
+
+    #[test]
+    fn zip_blob_with_file_name_too_long() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let file_name = "a".repeat(65536);
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (file_name.clone(), FileDescriptor { body: vec![1, 2, 3], ..FileDescriptor::default() }),
+        ];
+
+        let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
+
+        assert_eq!(ZipError::FileNameTooLong { file_name }, zip_error);
+    }
+
+    #[test]
+    fn zip_blob_with_longest_file_name() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            ("a".repeat(65535), FileDescriptor { body: vec![1, 2, 3], ..FileDescriptor::default() }),
+        ];
+
+        assert!(blob_factory_adapter.create_zip_binary(input_map).is_ok());
+    }
+
+    #[test]
+    fn create_zip_file_entry_with_unicode_path_extra_field() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unicode_path_extra_field: true, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("カプー.txt"), file_descriptor, 0);
+
+        assert_eq!(1, created_file_entry.local_extra_fields.len());
+        assert_eq!(0x7075, created_file_entry.local_extra_fields[0].header_id);
+        assert_eq!(1, created_file_entry.central_directory_extra_fields.len());
+        assert_eq!(0x7075, created_file_entry.central_directory_extra_fields[0].header_id);
+    }
+
+    #[test]
+    fn create_zip_file_entry_without_unicode_path_extra_field_for_ascii_name() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unicode_path_extra_field: true, ..ZipOptions::default() },
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
+
+        assert!(created_file_entry.local_extra_fields.is_empty());
+        assert!(created_file_entry.central_directory_extra_fields.is_empty());
+    }
+
+    #[test]
+    fn create_zip_file_entry_without_unicode_path_extra_field_by_default() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("カプー.txt"), file_descriptor, 0);
+
+        assert!(created_file_entry.local_extra_fields.is_empty());
+    }
+
+    #[test]
+    fn zip_blob_with_unicode_path_extra_field_too_long() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unicode_path_extra_field: true, ..ZipOptions::default() },
+        };
+
+        let file_name = "猫".repeat(21845);
+
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (file_name.clone(), FileDescriptor { body: vec![1, 2, 3], ..FileDescriptor::default() }),
+        ];
+
+        let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
+
+        assert_eq!(ZipError::ExtraFieldTooLong { file_name }, zip_error);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_store_directory_with_directory_attribute() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let file_descriptor = FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() };
+
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Logs/"), file_descriptor, 0);
+
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+        assert_eq!(Vec::<u8>::new(), created_file_entry.body);
+        assert_eq!(0x10, created_file_entry.external_file_attributes);
+    }
+
+    #[test]
+    fn create_zip_file_entries_should_add_trailing_slash_to_directories() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let given_directory_mapping: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Logs"), FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }),
+        ];
+
+        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_directory_mapping, &CentralDirectorySummary::default(), &mut HashSet::new()).unwrap();
+
+        assert_eq!(1, created_file_entries.len());
+        assert_eq!("Logs/", created_file_entries[0].file_name);
+    }
+
+    #[test]
+    fn create_zip_file_entries_should_add_parent_directories() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let given_directory_mapping: Vec<(String, FileDescriptor)> = vec![
+            (String::from("BugCat.txt"), FileDescriptor { body: vec![2; 16], ..FileDescriptor::default() }),
+            (String::from("Cats/Capoo/FoamCat.txt"), FileDescriptor { body: vec![0; 11], ..FileDescriptor::default() }),
+            (String::from("Cats/"), FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }),
+        ];
+
+        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_directory_mapping, &CentralDirectorySummary::default(), &mut HashSet::new()).unwrap();
+
+        let file_names: Vec<&str> = created_file_entries.iter().map(|entry| entry.file_name.as_str()).collect();
+
+        assert_eq!(vec!["BugCat.txt", "Cats/", "Cats/Capoo/", "Cats/Capoo/FoamCat.txt"], file_names);
+    }
+
+    #[test]
+    fn create_zip_file_entries_without_directory_entries() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { directory_entries: false, ..ZipOptions::default() },
+        };
+
+        let given_directory_mapping: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Cats/Capoo/FoamCat.txt"), FileDescriptor { body: vec![0; 11], ..FileDescriptor::default() }),
+            (String::from("Logs/"), FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }),
+        ];
+
+        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_directory_mapping, &CentralDirectorySummary::default(), &mut HashSet::new()).unwrap();
+
+        assert_eq!(1, created_file_entries.len());
+        assert_eq!("Cats/Capoo/FoamCat.txt", created_file_entries[0].file_name);
+    }
+
+    #[test]
+    fn create_zip_file_entries_should_preserve_directory_mapping_order() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let given_directory_mapping: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Zebra.txt"), FileDescriptor { body: vec![1], ..FileDescriptor::default() }),
+            (String::from("Cats/FoamCat.txt"), FileDescriptor { body: vec![2], ..FileDescriptor::default() }),
+            (String::from("Apple.txt"), FileDescriptor { body: vec![3], ..FileDescriptor::default() }),
+            (String::from("Cats/BugCat.txt"), FileDescriptor { body: vec![4], ..FileDescriptor::default() }),
+        ];
+
+        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_directory_mapping, &CentralDirectorySummary::default(), &mut HashSet::new()).unwrap();
+
+        let file_names: Vec<&str> = created_file_entries.iter().map(|entry| entry.file_name.as_str()).collect();
+
+        assert_eq!(vec!["Zebra.txt", "Cats/", "Cats/FoamCat.txt", "Apple.txt", "Cats/BugCat.txt"], file_names);
+    }
+
+    #[test]
+    fn zip_blob_should_be_identical_for_identical_directory_mappings() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let get_directory_mapping = || -> Vec<(String, FileDescriptor)> {
+            (0..64).map(|index| (format!("Folder{}/Capoo{}.txt", index % 4, index), FileDescriptor { body: vec![index; 32], ..FileDescriptor::default() })).collect()
+        };
+
+        let first_zip_blob = blob_factory_adapter.create_zip_binary(get_directory_mapping()).unwrap();
+        let second_zip_blob = blob_factory_adapter.create_zip_binary(get_directory_mapping()).unwrap();
+
+        assert_eq!(first_zip_blob, second_zip_blob);
+    }
+
+    #[test]
+    fn create_zip_file_entry_should_convert_last_modified_with_date_time_calculator() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
+
+        let file_descriptor = FileDescriptor { body: vec![7; 40], last_modified: Some(1654424430000), ..FileDescriptor::default() };
 
-    struct FakeCrcCalculator {}
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-    impl CrcCalculator for FakeCrcCalculator {
-        fn calculate_crc32(&self, _data: &[u8]) -> u32 {
-            0x11223344
-        }
+        assert_eq!(30, created_file_entry.dos_time);
+        assert_eq!(5, created_file_entry.dos_date);
     }
 
-    struct FakeDosDateTimeRetriever {}
+    #[test]
+    fn default_and_last_modified_entries_share_a_time_zone() {
+        let to_local_date_time: fn(i64) -> SystemTime = |unix_time_millis| SystemTime::from_unix_time_millis(unix_time_millis + 9 * 3600 * 1000);
 
-    impl DosDateTimeCalculator for FakeDosDateTimeRetriever {
-        fn get_current_dos_time(&self) -> u16 {
-            0x1133
-        }
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(DosDateTimeCalculatorAdapter {
+                date_time: to_local_date_time(1654424430000),
+                unix_time_millis: 1654424430000,
+                to_date_time: to_local_date_time,
+            }),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
 
-        fn get_current_dos_date(&self) -> u16 {
-            0x9988
-        }
+        let default_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), FileDescriptor::default(), 0);
+        let last_modified_file_entry = blob_factory_adapter.create_zip_file_entry(
+            String::from("Bugcat.txt"),
+            FileDescriptor { last_modified: Some(1654424430000), ..FileDescriptor::default() },
+            0,
+        );
+
+        assert_eq!(default_file_entry.dos_time, last_modified_file_entry.dos_time);
+        assert_eq!(default_file_entry.dos_date, last_modified_file_entry.dos_date);
+        assert_eq!(19, last_modified_file_entry.dos_time >> 11);
     }
 
     #[test]
-    fn zip_file_size_should_be_the_sum_of_body_header_lengths() {
+    fn create_zip_file_entry_with_extended_timestamp_extra_field() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { extended_timestamp_extra_field: true, ..ZipOptions::default() },
         };
 
-        let fake_file_entry = ZipFileEntry {
-            body: vec![0; 293],
-            crc: 0,
-            file_name: String::from("blab"),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
+        let file_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            last_modified: Some(287454020000),
+            last_accessed: Some(1432778632000),
+            ..FileDescriptor::default()
         };
 
-        assert_eq!(327, blob_factory_adapter.get_zip_file_size(&fake_file_entry));
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
+
+        assert_eq!(1, created_file_entry.local_extra_fields.len());
+        assert_eq!(0x5455, created_file_entry.local_extra_fields[0].header_id);
+        assert_eq!(vec![0x03, 0x44, 0x33, 0x22, 0x11, 0x88, 0x77, 0x66, 0x55], created_file_entry.local_extra_fields[0].data);
+        assert_eq!(1, created_file_entry.central_directory_extra_fields.len());
+        assert_eq!(vec![0x03, 0x44, 0x33, 0x22, 0x11], created_file_entry.central_directory_extra_fields[0].data);
     }
 
     #[test]
-    fn calculate_file_crc_should_delegate_to_crc_calculator() {
+    fn create_zip_file_entry_with_extended_timestamp_extra_field_at_current_time() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { extended_timestamp_extra_field: true, ..ZipOptions::default() },
         };
 
-        let fake_file_body: Vec<u8> = vec![0; 33];
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
 
-        assert_eq!(0x11223344, blob_factory_adapter.calculate_file_crc(&fake_file_body));
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
+
+        assert_eq!(vec![0x01, 0x2E, 0x8A, 0xA0, 0x62], created_file_entry.local_extra_fields[0].data);
     }
 
     #[test]
-    fn create_zip_file_entry_should_create_zip_entry_with_given_data() {
+    fn create_zip_file_entry_without_extended_timestamp_extra_field_by_default() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let file_name = String::from("BugCat");
-        let file_body: Vec<u8> = vec![0; 33];
-        let header_offset: u32 = 98;
+        let file_descriptor = FileDescriptor { body: vec![7; 40], last_modified: Some(0x11223344), ..FileDescriptor::default() };
 
-        let created_file_entry = blob_factory_adapter.create_zip_file_entry(file_name.clone(), file_body.clone(), header_offset);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        assert_eq!(file_name, created_file_entry.file_name);
-        assert_eq!(file_body, created_file_entry.body);
-        assert_eq!(header_offset, created_file_entry.entry_offset);
-        assert_eq!(0x11223344, created_file_entry.crc);
-        assert_eq!(0x1133, created_file_entry.dos_time);
-        assert_eq!(0x9988, created_file_entry.dos_date);
+        assert!(created_file_entry.local_extra_fields.is_empty());
+        assert!(created_file_entry.central_directory_extra_fields.is_empty());
     }
 
     #[test]
-    fn creating_multiple_entries_from_directory_hash_map_with_sequential_header_offsets() {
+    fn create_zip_file_entry_with_ntfs_extra_field() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { ntfs_extra_field: true, ..ZipOptions::default() },
         };
 
-        let given_hash_map: HashMap<String, Vec<u8>> = HashMap::from([
-            (String::from("BugCat.txt"), vec![2; 16]),
-            (String::from("MyFolder/FoamCat.txt"), vec![0; 11])
-        ]);
-
-        let created_file_entries = blob_factory_adapter.create_zip_file_entries(given_hash_map);
+        let file_descriptor = FileDescriptor { body: vec![7; 40], last_modified: Some(0), ..FileDescriptor::default() };
 
-        //The consuming iterator used to create the vector has arbitrary ordering
-        if created_file_entries[0].file_name == String::from("BugCat.txt") {
-            assert_eq!(vec![2; 16], created_file_entries[0].body);
-            assert_eq!(0, created_file_entries[0].entry_offset);
-
-            assert_eq!(vec![0; 11], created_file_entries[1].body);
-            assert_eq!(56, created_file_entries[1].entry_offset);
-        } else {
-            assert_eq!(vec![0; 11], created_file_entries[0].body);
-            assert_eq!(0, created_file_entries[0].entry_offset);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-            assert_eq!(vec![2; 16], created_file_entries[1].body);
-            assert_eq!(61, created_file_entries[1].entry_offset);
-        }
+        assert_eq!(1, created_file_entry.local_extra_fields.len());
+        assert_eq!(0x000A, created_file_entry.local_extra_fields[0].header_id);
+        assert_eq!(&[0x00, 0x80, 0x3E, 0xD5, 0xDE, 0xB1, 0x9D, 0x01], &created_file_entry.local_extra_fields[0].data[8..16]);
+        assert_eq!(1, created_file_entry.central_directory_extra_fields.len());
+        assert_eq!(0x000A, created_file_entry.central_directory_extra_fields[0].header_id);
     }
 
     #[test]
-    fn number_of_central_directory_records_with_one_entry() {
+    fn create_zip_file_entry_without_unix_mode_by_default() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![],
-            crc: 0,
-            file_name: "a".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
 
-        let number_of_central_directory_records = blob_factory_adapter.get_number_of_central_directory_records_section(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        assert_eq!([0x01, 0x00], &number_of_central_directory_records[0..2]);
+        assert_eq!(0, created_file_entry.external_file_attributes);
+        assert_eq!(0x003F, created_file_entry.version_made_by);
     }
 
     #[test]
-    fn number_of_central_directory_records_with_many_entries() {
+    fn create_zip_file_entry_with_unix_mode() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        for _entry_number in 0..1000 {
-            zip_entries.push(ZipFileEntry {
-                body: vec![],
-                crc: 0,
-                file_name: "a".to_string(),
-                dos_time: 0,
-                dos_date: 0,
-                entry_offset: 0,
-            });
-        }
+        let file_descriptor = FileDescriptor { body: vec![7; 40], unix_mode: Some(0o755), ..FileDescriptor::default() };
 
-        let number_of_central_directory_records = blob_factory_adapter.get_number_of_central_directory_records_section(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("run.sh"), file_descriptor, 0);
 
-        assert_eq!([0xE8, 0x03], &number_of_central_directory_records[0..2]);
+        assert_eq!(0o100755 << 16, created_file_entry.external_file_attributes);
+        assert_eq!(0x033F, created_file_entry.version_made_by);
     }
 
     #[test]
-    fn central_directory_size_with_one_entry() {
+    fn create_zip_file_entry_with_unix_mode_ignores_file_type_bits() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
+        let file_descriptor = FileDescriptor { body: vec![7; 40], unix_mode: Some(0o120600), ..FileDescriptor::default() };
 
-        let central_directory_size = blob_factory_adapter.get_size_of_central_directory_section(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("secret.txt"), file_descriptor, 0);
 
-        assert_eq!([0x38, 0x00, 0x00, 0x00], &central_directory_size[0..4]);
+        assert_eq!(0o100600 << 16, created_file_entry.external_file_attributes);
     }
 
     #[test]
-    fn central_directory_size_with_many_entries() {
+    fn create_zip_file_entry_with_default_unix_file_permissions() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unix_permissions: true, ..ZipOptions::default() },
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        for _entry_number in 0..10 {
-            zip_entries.push(ZipFileEntry {
-                body: vec![0; 10],
-                crc: 0,
-                file_name: "BugCat.txt".to_string(),
-                dos_time: 0,
-                dos_date: 0,
-                entry_offset: 0,
-            });
-        }
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
 
-        let central_directory_size = blob_factory_adapter.get_size_of_central_directory_section(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        assert_eq!([0x30, 0x02, 0x00, 0x00], &central_directory_size[0..4]);
+        assert_eq!(0o100644 << 16, created_file_entry.external_file_attributes);
+        assert_eq!(0x033F, created_file_entry.version_made_by);
     }
 
     #[test]
-    fn central_directory_offset_with_one_entry() {
+    fn create_zip_file_entry_with_default_unix_directory_permissions() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unix_permissions: true, ..ZipOptions::default() },
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
+        let file_descriptor = FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() };
 
-        let central_directory_offset = blob_factory_adapter.get_central_directory_start_offset_section(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Logs/"), file_descriptor, 0);
 
-        assert_eq!([0x32, 0x00, 0x00, 0x00], &central_directory_offset[0..4]);
+        assert_eq!((0o040755 << 16) | 0x10, created_file_entry.external_file_attributes);
     }
 
     #[test]
-    fn end_of_central_directory_signature() {
+    fn create_zip_file_entry_should_store_symlink_with_link_target() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        let file_descriptor = FileDescriptor {
+            body: Vec::from("../shared/config.json".as_bytes()),
+            entry_type: EntryType::Symlink,
+            ..FileDescriptor::default()
+        };
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("config.json"), file_descriptor, 0);
 
-        assert_eq!([0x50, 0x4B, 0x05, 0x06], &end_of_central_directory[0..4]);
+        assert_eq!(CompressionMethod::Stored, created_file_entry.compression_method);
+        assert_eq!(Vec::from("../shared/config.json".as_bytes()), created_file_entry.body);
+        assert_eq!(0o120777 << 16, created_file_entry.external_file_attributes);
+        assert_eq!(0x033F, created_file_entry.version_made_by);
     }
 
     #[test]
-    fn end_of_central_directory_number_of_disk() {
+    fn create_zip_file_entry_should_store_symlink_with_unix_mode() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        let file_descriptor = FileDescriptor {
+            body: Vec::from("run.sh".as_bytes()),
+            entry_type: EntryType::Symlink,
+            unix_mode: Some(0o755),
+            ..FileDescriptor::default()
+        };
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("start"), file_descriptor, 0);
 
-        assert_eq!([0x00, 0x00], &end_of_central_directory[4..6]);
+        assert_eq!(0o120755 << 16, created_file_entry.external_file_attributes);
     }
 
     #[test]
-    fn end_of_central_directory_disk_where_central_directory_starts() {
+    fn create_zip_file_entry_with_unix_owner_extra_field() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
+        let file_descriptor = FileDescriptor { body: vec![7; 40], uid: Some(1000), ..FileDescriptor::default() };
 
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
-
-        assert_eq!([0x00, 0x00], &end_of_central_directory[6..8]);
+        assert_eq!(1, created_file_entry.local_extra_fields.len());
+        assert_eq!(0x7875, created_file_entry.local_extra_fields[0].header_id);
+        assert_eq!(vec![0x01, 0x04, 0xE8, 0x03, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00], created_file_entry.local_extra_fields[0].data);
+        assert_eq!(1, created_file_entry.central_directory_extra_fields.len());
+        assert_eq!(0x7875, created_file_entry.central_directory_extra_fields[0].header_id);
     }
 
     #[test]
-    fn end_of_central_directory_number_of_records_on_disk() {
+    fn create_zip_file_entry_without_unix_owner_extra_field() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { unix_permissions: true, ..ZipOptions::default() },
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        let file_descriptor = FileDescriptor { body: vec![7; 40], ..FileDescriptor::default() };
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        assert_eq!([0x01, 0x00], &end_of_central_directory[8..10]);
+        assert!(created_file_entry.local_extra_fields.is_empty());
+        assert!(created_file_entry.central_directory_extra_fields.is_empty());
     }
 
     #[test]
-    fn end_of_central_directory_total_number_of_records() {
+    fn create_zip_file_entry_with_custom_extra_fields() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { extended_timestamp_extra_field: true, ..ZipOptions::default() },
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        let file_descriptor = FileDescriptor {
+            body: vec![7; 40],
+            local_extra_fields: vec![ExtraField { header_id: 0xCAFE, data: vec![0x01, 0x02] }],
+            central_directory_extra_fields: vec![ExtraField { header_id: 0xBEEF, data: vec![0x03] }],
+            ..FileDescriptor::default()
+        };
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let created_file_entry = blob_factory_adapter.create_zip_file_entry(String::from("Capoo.txt"), file_descriptor, 0);
 
-        assert_eq!([0x01, 0x00], &end_of_central_directory[10..12]);
+        assert_eq!(2, created_file_entry.local_extra_fields.len());
+        assert_eq!(0x5455, created_file_entry.local_extra_fields[0].header_id);
+        assert_eq!(0xCAFE, created_file_entry.local_extra_fields[1].header_id);
+        assert_eq!(vec![0x01, 0x02], created_file_entry.local_extra_fields[1].data);
+        assert_eq!(2, created_file_entry.central_directory_extra_fields.len());
+        assert_eq!(0xBEEF, created_file_entry.central_directory_extra_fields[1].header_id);
+        assert_eq!(vec![0x03], created_file_entry.central_directory_extra_fields[1].data);
     }
 
     #[test]
-    fn end_of_central_directory_size_of_central_directory() {
+    fn zip_blob_with_custom_extra_fields_too_long() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: vec![1, 2, 3],
+                central_directory_extra_fields: vec![
+                    ExtraField { header_id: 0xCAFE, data: vec![0; 40000] },
+                    ExtraField { header_id: 0xBEEF, data: vec![0; 40000] },
+                ],
+                ..FileDescriptor::default()
+            }),
+        ];
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
 
-        assert_eq!([0x38, 0x00, 0x00, 0x00], &end_of_central_directory[12..16]);
+        assert_eq!(ZipError::ExtraFieldTooLong { file_name: String::from("Capoo.txt") }, zip_error);
     }
 
     #[test]
-    fn end_of_central_directory_start_offset_of_central_directory() {
+    fn zip_blob_with_longest_custom_extra_field() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: vec![1, 2, 3],
+                local_extra_fields: vec![ExtraField { header_id: 0xCAFE, data: vec![0; 65531] }],
+                ..FileDescriptor::default()
+            }),
+        ];
 
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
+        assert!(blob_factory_adapter.create_zip_binary(input_map).is_ok());
+    }
+
+    #[test]
+    fn zip_blob_with_reserved_custom_extra_field() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
+        };
 
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: vec![1, 2, 3],
+                local_extra_fields: vec![ExtraField { header_id: 0x0001, data: vec![0; 8] }],
+                ..FileDescriptor::default()
+            }),
+        ];
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+        let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
 
-        assert_eq!([0x32, 0x00, 0x00, 0x00], &end_of_central_directory[16..20]);
+        assert_eq!(ZipError::ReservedExtraField { file_name: String::from("Capoo.txt"), header_id: 0x0001 }, zip_error);
     }
 
     #[test]
-    fn end_of_central_directory_start_comment_length() {
+    fn zip_blob_with_custom_extra_fields_written_by_the_factory() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let mut zip_entries: Vec<ZipFileEntry> = Vec::new();
-
-        zip_entries.push(ZipFileEntry {
-            body: vec![0; 10],
-            crc: 0,
-            file_name: "BugCat.txt".to_string(),
-            dos_time: 0,
-            dos_date: 0,
-            entry_offset: 0,
-        });
-
+        for header_id in [0x000A, 0x5455, 0x7075, 0x7875] {
+            let input_map: Vec<(String, FileDescriptor)> = vec![
+                (String::from("Capoo.txt"), FileDescriptor {
+                    body: vec![1, 2, 3],
+                    central_directory_extra_fields: vec![ExtraField { header_id, data: vec![0; 5] }],
+                    ..FileDescriptor::default()
+                }),
+            ];
 
-        let end_of_central_directory = blob_factory_adapter.get_end_of_central_directory_record(&zip_entries);
+            let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
 
-        assert_eq!([0x00, 0x00], &end_of_central_directory[20..22]);
+            assert_eq!(ZipError::ReservedExtraField { file_name: String::from("Capoo.txt"), header_id }, zip_error);
+        }
     }
 
     #[test]
-    fn single_file_zip_blob_header_signatures() {
+    fn zip_blob_with_archive_comment_too_long() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { comment: "a".repeat(0x10000), ..ZipOptions::default() },
         };
 
-        let file_contents = String::from("Capoo is Hungry.");
+        let zip_error = blob_factory_adapter.create_zip_binary(Vec::new()).unwrap_err();
 
-        let input_map: HashMap<String, Vec<u8>> = HashMap::from([
-            (String::from("Hello.txt"), Vec::from(file_contents.as_bytes())),
-        ]);
+        assert_eq!(ZipError::ArchiveCommentTooLong, zip_error);
+    }
 
-        let zip_blob = blob_factory_adapter.create_zip_binary(input_map);
+    #[test]
+    fn zip_blob_with_archive_comment_containing_signature() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { comment: String::from("BugCat PK\u{5}\u{6} Capoo"), ..ZipOptions::default() },
+        };
 
-        assert_eq!(132, zip_blob.len());
+        let zip_error = blob_factory_adapter.create_zip_binary(Vec::new()).unwrap_err();
 
-        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
-        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[55..59]);
-        assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[110..114]);
+        assert_eq!(ZipError::ArchiveCommentContainsSignature, zip_error);
     }
 
     #[test]
-    fn multiple_file_zip_blob_header_signatures() {
+    fn zip_blob_with_file_comment_too_long() {
         let blob_factory_adapter = ZipBlobFactoryAdapter {
             crc_calculator: Box::new(FakeCrcCalculator {}),
             date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions::default(),
         };
 
-        let input_map: HashMap<String, Vec<u8>> = HashMap::from([
-            (String::from("Hello.txt"), Vec::from(String::from("Capoo is Hungry.").as_bytes())),
-            (String::from("Folder/Hi.csv"), Vec::from([1, 2, 3])),
-        ]);
-
-        let zip_blob = blob_factory_adapter.create_zip_binary(input_map);
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: vec![1, 2, 3],
+                comment: Some("a".repeat(0x10000)),
+                ..FileDescriptor::default()
+            }),
+        ];
 
-        assert_eq!(237, zip_blob.len());
+        let zip_error = blob_factory_adapter.create_zip_binary(input_map).unwrap_err();
 
-        //The consuming iterator used to create the file entry vector has arbitrary ordering
-        if &zip_blob[39..55] == String::from("Capoo is Hungry.").as_bytes() {
-            assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
-            assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[101..105]);
+        assert_eq!(ZipError::FileCommentTooLong { file_name: String::from("Capoo.txt") }, zip_error);
+    }
 
-            assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[55..59]);
-            assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[156..160]);
+    #[test]
+    fn zip_blob_with_data_descriptors() {
+        let blob_factory_adapter = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(FakeCrcCalculator {}),
+            date_time_retriever: Box::new(FakeDosDateTimeRetriever {}),
+            compressor: Box::new(FakeCompressor {}),
+            options: ZipOptions { compression_method: CompressionMethod::Stored, data_descriptor: true, ..ZipOptions::default() },
+        };
 
-            assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[215..219]);
-        } else {
-            assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[0..4]);
-            assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[101..105]);
+        let input_map: Vec<(String, FileDescriptor)> = vec![
+            (String::from("Capoo.txt"), FileDescriptor { body: vec![1, 2, 3], ..FileDescriptor::default() }),
+            (String::from("BugCat.txt"), FileDescriptor { body: vec![4, 5], ..FileDescriptor::default() }),
+        ];
 
-            assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[46..50]);
-            assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[160..164]);
+        let zip_blob = blob_factory_adapter.create_zip_binary(input_map).unwrap();
 
-            assert_eq!([0x50, 0x4B, 0x05, 0x06], &zip_blob[215..219]);
-        }
+        assert_eq!([0x50, 0x4B, 0x07, 0x08], &zip_blob[42..46]);
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_blob[58..62]);
+        assert_eq!([0x50, 0x4B, 0x07, 0x08], &zip_blob[100..104]);
+        assert_eq!([0x50, 0x4B, 0x01, 0x02], &zip_blob[116..120]);
+        assert_eq!([58, 0x00, 0x00, 0x00], &zip_blob[213..217]);
     }
 }
\ No newline at end of file
@@ -1,18 +1,38 @@
+use crate::zip_file::{CompressionMethod, LANGUAGE_ENCODING_FLAG, ZIP64_FIELD_LIMIT, ZIP64_VERSION_NEEDED_TO_EXTRACT};
+use crate::zip_file::extra_field::{ExtraField, ZIP64_EXTRA_FIELD_ID};
+
+const DATA_DESCRIPTOR_FLAG: u16 = 1 << 3;
+const DIRECTORY_VERSION_NEEDED_TO_EXTRACT: u16 = 0x0014;
+pub const VERSION_MADE_BY_MS_DOS: u16 = 0x003F;
+pub const VERSION_MADE_BY_UNIX: u16 = 0x033F;
+
 pub struct ZipFileEntry {
     pub body: Vec<u8>,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub compression_method: CompressionMethod,
     pub crc: u32,
     pub file_name: String,
     pub dos_time: u16,
     pub dos_date: u16,
-    pub entry_offset: u32,
+    pub entry_offset: u64,
+    pub force_zip64: bool,
+    pub data_descriptor: bool,
+    pub data_descriptor_signature: bool,
+    pub local_extra_fields: Vec<ExtraField>,
+    pub central_directory_extra_fields: Vec<ExtraField>,
+    pub external_file_attributes: u32,
+    pub version_made_by: u16,
+    pub comment: String,
 }
 
 impl ZipFileEntry {
     pub fn get_local_file_header_size(&self) -> usize {
         let base_header_size = 30;
         let file_name_size = self.file_name.len();
+        let extra_field_size = self.get_local_extra_field().len();
 
-        base_header_size + file_name_size
+        base_header_size + file_name_size + extra_field_size
     }
 
     pub fn get_local_file_header(&self) -> Vec<u8> {
@@ -20,28 +40,68 @@ impl ZipFileEntry {
 
         let mut local_file_archive_details_section = self.get_local_file_archive_details_section();
         let mut modified_time_header_section = self.get_modified_time_header_section();
-        let mut crc_and_size_header_section = self.get_crc_and_size_header_section();
+        let mut crc_and_size_header_section = self.get_local_crc_and_size_header_section();
         let mut local_file_name_header_section = self.get_local_file_name_header_section();
+        let mut local_extra_field = self.get_local_extra_field();
 
         local_file_header.append(&mut local_file_archive_details_section);
         local_file_header.append(&mut modified_time_header_section);
         local_file_header.append(&mut crc_and_size_header_section);
         local_file_header.append(&mut local_file_name_header_section);
+        local_file_header.append(&mut local_extra_field);
 
         local_file_header
     }
 
     fn get_local_file_archive_details_section(&self) -> Vec<u8> {
-        let archive_details_section: Vec<u8> = vec![
+        let mut archive_details_section: Vec<u8> = vec![
             0x50, 0x4B, 0x03, 0x04,     // local file header signature
-            0x0A, 0x00,                 // version needed to extract
-            0x00, 0x00,                 // general purpose bit flag
-            0x00, 0x00,                 // compression method
         ];
 
+        let mut version_needed_to_extract = self.get_version_needed_to_extract_section();
+        let mut general_purpose_bit_flag = self.get_general_purpose_bit_flag_section();
+        let mut compression_method = self.get_compression_method_section();
+
+        archive_details_section.append(&mut version_needed_to_extract);
+        archive_details_section.append(&mut general_purpose_bit_flag);
+        archive_details_section.append(&mut compression_method);
+
         archive_details_section
     }
 
+    fn get_version_needed_to_extract_section(&self) -> Vec<u8> {
+        let mut version_needed_to_extract = self.compression_method.get_version_needed_to_extract();
+
+        if self.file_name.ends_with('/') {
+            version_needed_to_extract = version_needed_to_extract.max(DIRECTORY_VERSION_NEEDED_TO_EXTRACT);
+        }
+
+        if self.has_zip64_sizes() || self.has_zip64_offset() {
+            version_needed_to_extract = version_needed_to_extract.max(ZIP64_VERSION_NEEDED_TO_EXTRACT);
+        }
+
+        Vec::from(version_needed_to_extract.to_le_bytes())
+    }
+
+    fn get_general_purpose_bit_flag_section(&self) -> Vec<u8> {
+        let mut general_purpose_bit_flag: u16 = 0;
+
+        if self.data_descriptor {
+            general_purpose_bit_flag |= DATA_DESCRIPTOR_FLAG;
+        }
+
+        //Without this flag, readers assume the name and comment are encoded with the IBM PC character set
+        if !self.file_name.is_ascii() || !self.comment.is_ascii() {
+            general_purpose_bit_flag |= LANGUAGE_ENCODING_FLAG;
+        }
+
+        Vec::from(general_purpose_bit_flag.to_le_bytes())
+    }
+
+    fn get_compression_method_section(&self) -> Vec<u8> {
+        Vec::from(self.compression_method.get_method_id().to_le_bytes())
+    }
+
     fn get_modified_time_header_section(&self) -> Vec<u8> {
         let mut last_modified_time = Vec::from(self.dos_time.to_le_bytes());
         let mut last_modified_date = Vec::from(self.dos_date.to_le_bytes());
@@ -51,12 +111,27 @@ impl ZipFileEntry {
         last_modified_time
     }
 
+    //With a data descriptor, the crc and sizes are only known once the body has been written
+    fn get_local_crc_and_size_header_section(&self) -> Vec<u8> {
+        if !self.data_descriptor {
+            return self.get_crc_and_size_header_section();
+        }
+
+        let mut crc_and_size_header_section: Vec<u8> = vec![0x00; 4];
+        let size_placeholder = self.get_zip32_field(0, self.has_zip64_sizes());
+
+        crc_and_size_header_section.extend_from_slice(&size_placeholder.to_le_bytes());
+        crc_and_size_header_section.extend_from_slice(&size_placeholder.to_le_bytes());
+
+        crc_and_size_header_section
+    }
+
     fn get_crc_and_size_header_section(&self) -> Vec<u8> {
         let mut crc_and_size_header_section: Vec<u8> = Vec::with_capacity(12);
 
         let mut crc = Vec::from(self.crc.to_le_bytes());
-        let mut compressed_size = Vec::from(self.get_total_file_size().to_le_bytes());
-        let mut uncompressed_size = Vec::from(self.get_total_file_size().to_le_bytes());
+        let mut compressed_size = Vec::from(self.get_zip32_field(self.get_total_file_size(), self.has_zip64_sizes()).to_le_bytes());
+        let mut uncompressed_size = Vec::from(self.get_zip32_field(self.uncompressed_size, self.has_zip64_sizes()).to_le_bytes());
 
         crc_and_size_header_section.append(&mut crc);
         crc_and_size_header_section.append(&mut compressed_size);
@@ -65,10 +140,122 @@ impl ZipFileEntry {
         crc_and_size_header_section
     }
 
-    fn get_total_file_size(&self) -> u32 {
-        let body_size: u32 = self.body.len() as u32;
+    fn get_total_file_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    fn has_zip64_sizes(&self) -> bool {
+        self.force_zip64
+            || self.uncompressed_size >= ZIP64_FIELD_LIMIT
+            || self.get_total_file_size() >= ZIP64_FIELD_LIMIT
+    }
+
+    fn has_zip64_offset(&self) -> bool {
+        self.force_zip64 || self.entry_offset >= ZIP64_FIELD_LIMIT
+    }
+
+    //Fields that do not fit are moved into the zip64 extra field, leaving a placeholder in the header
+    fn get_zip32_field(&self, value: u64, is_zip64: bool) -> u32 {
+        if is_zip64 {
+            return ZIP64_FIELD_LIMIT as u32;
+        }
+
+        value as u32
+    }
+
+    pub fn get_local_extra_field_size(&self) -> usize {
+        self.get_local_extra_field().len()
+    }
+
+    pub fn get_central_directory_extra_field_size(&self) -> usize {
+        self.get_central_directory_extra_field().len()
+    }
+
+    fn get_local_extra_field(&self) -> Vec<u8> {
+        let mut zip64_values: Vec<u64> = Vec::new();
+
+        if self.has_zip64_sizes() && self.data_descriptor {
+            zip64_values.push(0);
+            zip64_values.push(0);
+        } else if self.has_zip64_sizes() {
+            zip64_values.push(self.uncompressed_size);
+            zip64_values.push(self.get_total_file_size());
+        }
+
+        self.get_extra_field_bytes(&zip64_values, &self.local_extra_fields)
+    }
+
+    fn get_central_directory_extra_field(&self) -> Vec<u8> {
+        let mut zip64_values: Vec<u64> = Vec::new();
+
+        if self.has_zip64_sizes() {
+            zip64_values.push(self.uncompressed_size);
+            zip64_values.push(self.get_total_file_size());
+        }
+
+        if self.has_zip64_offset() {
+            zip64_values.push(self.entry_offset);
+        }
+
+        self.get_extra_field_bytes(&zip64_values, &self.central_directory_extra_fields)
+    }
+
+    fn get_extra_field_bytes(&self, zip64_values: &[u64], extra_fields: &[ExtraField]) -> Vec<u8> {
+        let mut extra_field_bytes: Vec<u8> = Vec::new();
+
+        if !zip64_values.is_empty() {
+            let mut zip64_extra_field = self.get_zip64_extra_field(zip64_values).get_bytes();
+            extra_field_bytes.append(&mut zip64_extra_field);
+        }
+
+        for extra_field in extra_fields {
+            let mut extra_field = extra_field.get_bytes();
+            extra_field_bytes.append(&mut extra_field);
+        }
+
+        extra_field_bytes
+    }
+
+    fn get_zip64_extra_field(&self, zip64_values: &[u64]) -> ExtraField {
+        let mut data: Vec<u8> = Vec::with_capacity(zip64_values.len() * 8);
+
+        for zip64_value in zip64_values {
+            data.extend_from_slice(&zip64_value.to_le_bytes());
+        }
+
+        ExtraField {
+            header_id: ZIP64_EXTRA_FIELD_ID,
+            data,
+        }
+    }
+
+    pub fn get_data_descriptor_size(&self) -> usize {
+        self.get_data_descriptor().len()
+    }
+
+    pub fn get_data_descriptor(&self) -> Vec<u8> {
+        if !self.data_descriptor {
+            return Vec::new();
+        }
+
+        let mut data_descriptor: Vec<u8> = Vec::with_capacity(24);
+
+        if self.data_descriptor_signature {
+            data_descriptor.extend_from_slice(&[0x50, 0x4B, 0x07, 0x08]);
+        }
+
+        data_descriptor.extend_from_slice(&self.crc.to_le_bytes());
+
+        //Archives with zip64 sizes use 8 byte sizes in the data descriptor as well
+        if self.has_zip64_sizes() {
+            data_descriptor.extend_from_slice(&self.get_total_file_size().to_le_bytes());
+            data_descriptor.extend_from_slice(&self.uncompressed_size.to_le_bytes());
+        } else {
+            data_descriptor.extend_from_slice(&(self.get_total_file_size() as u32).to_le_bytes());
+            data_descriptor.extend_from_slice(&(self.uncompressed_size as u32).to_le_bytes());
+        }
 
-        body_size
+        data_descriptor
     }
 
     fn get_local_file_name_header_section(&self) -> Vec<u8> {
@@ -77,7 +264,8 @@ impl ZipFileEntry {
         let name_length = self.file_name.len() as u16;
         let mut file_name_length = Vec::from(name_length.to_le_bytes());
 
-        let mut extra_field_length = vec![0x00, 0x00];
+        let extra_field_size = self.get_local_extra_field().len() as u16;
+        let mut extra_field_length = Vec::from(extra_field_size.to_le_bytes());
 
         let mut file_name = Vec::from(self.file_name.as_bytes());
 
@@ -90,8 +278,9 @@ impl ZipFileEntry {
 
     pub fn get_central_directory_header_size(&self) -> usize {
         let base_header_size = 46;
+        let extra_field_size = self.get_central_directory_extra_field().len();
 
-        base_header_size + self.file_name.len()
+        base_header_size + self.file_name.len() + extra_field_size + self.comment.len()
     }
 
     pub fn get_central_directory_header(&self) -> Vec<u8> {
@@ -101,24 +290,34 @@ impl ZipFileEntry {
         let mut modified_time_header_section = self.get_modified_time_header_section();
         let mut crc_and_size_header_section = self.get_crc_and_size_header_section();
         let mut central_directory_details_header_section = self.get_central_directory_details_header_section();
+        let mut central_directory_extra_field = self.get_central_directory_extra_field();
+        let mut file_comment = Vec::from(self.comment.as_bytes());
 
         central_directory_header.append(&mut central_directory_archive_header_section);
         central_directory_header.append(&mut modified_time_header_section);
         central_directory_header.append(&mut crc_and_size_header_section);
         central_directory_header.append(&mut central_directory_details_header_section);
+        central_directory_header.append(&mut central_directory_extra_field);
+        central_directory_header.append(&mut file_comment);
 
         central_directory_header
     }
 
     fn get_central_directory_archive_details_section(&self) -> Vec<u8> {
-        let archive_details_section: Vec<u8> = vec![
+        let mut archive_details_section: Vec<u8> = vec![
             0x50, 0x4B, 0x01, 0x02,     // central directory header signature
-            0x3F, 0x00,                 // version made by
-            0x0A, 0x00,                 // version needed to extract
-            0x00, 0x00,                 // general purpose bit flag
-            0x00, 0x00,                 // compression method
         ];
 
+        let mut version_made_by = Vec::from(self.version_made_by.to_le_bytes());
+        let mut version_needed_to_extract = self.get_version_needed_to_extract_section();
+        let mut general_purpose_bit_flag = self.get_general_purpose_bit_flag_section();
+        let mut compression_method = self.get_compression_method_section();
+
+        archive_details_section.append(&mut version_made_by);
+        archive_details_section.append(&mut version_needed_to_extract);
+        archive_details_section.append(&mut general_purpose_bit_flag);
+        archive_details_section.append(&mut compression_method);
+
         archive_details_section
     }
 
@@ -127,12 +326,13 @@ impl ZipFileEntry {
 
         let name_length = self.file_name.len() as u16;
         let mut file_name_length = Vec::from(name_length.to_le_bytes());
-        let mut extra_field_length = vec![0x00, 0x00];
-        let mut file_comment_length = vec![0x00, 0x00];
+        let extra_field_size = self.get_central_directory_extra_field().len() as u16;
+        let mut extra_field_length = Vec::from(extra_field_size.to_le_bytes());
+        let mut file_comment_length = Vec::from((self.comment.len() as u16).to_le_bytes());
         let mut disk_number_start = vec![0x00, 0x00];
         let mut internal_file_attributes = vec![0x00, 0x00];
-        let mut external_file_attributes = vec![0x00, 0x00, 0x00, 0x00];
-        let mut relative_offset = Vec::from(self.entry_offset.to_le_bytes());
+        let mut external_file_attributes = Vec::from(self.external_file_attributes.to_le_bytes());
+        let mut relative_offset = Vec::from(self.get_zip32_field(self.entry_offset, self.has_zip64_offset()).to_le_bytes());
         let mut file_name = Vec::from(self.file_name.as_bytes());
 
         name_header_section.append(&mut file_name_length);
@@ -156,11 +356,22 @@ mod tests {
     fn header_size_with_short_file_name() {
         let file_entry = ZipFileEntry {
             body: vec![0; 3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("yow"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let header_size = file_entry.get_local_file_header_size();
@@ -172,11 +383,22 @@ mod tests {
     fn header_size_with_long_file_name() {
         let file_entry = ZipFileEntry {
             body: vec![0; 3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo The BugCat Makes His Move"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let header_size = file_entry.get_local_file_header_size();
@@ -188,11 +410,22 @@ mod tests {
     fn local_file_header_signature() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -204,11 +437,22 @@ mod tests {
     fn local_file_header_minimum_version() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -220,11 +464,22 @@ mod tests {
     fn local_file_header_general_purpose_bit_flag() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -236,11 +491,22 @@ mod tests {
     fn local_file_header_compression_method() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -248,15 +514,108 @@ mod tests {
         assert_eq!([0x00, 0x00], &local_file_header[8..10]);
     }
 
+    #[test]
+    fn local_file_header_minimum_version_with_deflate() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 9,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x14, 0x00], &local_file_header[4..6]);
+    }
+
+    #[test]
+    fn local_file_header_compression_method_with_deflate() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 9,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x08, 0x00], &local_file_header[8..10]);
+    }
+
+    #[test]
+    fn local_file_header_sizes_with_deflate() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 900,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x03, 0x00, 0x00, 0x00], &local_file_header[18..22]);
+        assert_eq!([0x84, 0x03, 0x00, 0x00], &local_file_header[22..26]);
+    }
+
     #[test]
     fn local_file_header_modified_time() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0x5611,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -268,11 +627,22 @@ mod tests {
     fn local_file_header_modified_date() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0x88AC,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -284,11 +654,22 @@ mod tests {
     fn local_file_header_crc() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0x11223344,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0x88AC,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -300,11 +681,22 @@ mod tests {
     fn local_file_header_compressed_size() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -316,11 +708,22 @@ mod tests {
     fn local_file_header_uncompressed_size() {
         let file_entry = ZipFileEntry {
             body: vec![0;4],
+            compressed_size: 4,
+            uncompressed_size: 4,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Capoo The BugCat"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -332,11 +735,22 @@ mod tests {
     fn local_file_header_file_name_length() {
         let file_entry = ZipFileEntry {
             body: vec![0;4],
+            compressed_size: 4,
+            uncompressed_size: 4,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("FoamCat/CafeIsGood.txt"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -348,11 +762,22 @@ mod tests {
     fn local_file_header_extra_field_length() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("FoamCat"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -366,11 +791,22 @@ mod tests {
 
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: given_file_name.clone(),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let local_file_header = file_entry.get_local_file_header();
@@ -382,11 +818,22 @@ mod tests {
     fn central_directory_header_size_with_small_file_name() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("yo"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let header_size = file_entry.get_central_directory_header_size();
@@ -398,11 +845,22 @@ mod tests {
     fn central_directory_header_size_with_large_file_name() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("This Is An Exceedingly Long File Name With Many Characters.txt"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let header_size = file_entry.get_central_directory_header_size();
@@ -414,11 +872,22 @@ mod tests {
     fn central_directory_header_signature() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("This Is An Exceedingly Long File Name With Many Characters.txt"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -430,11 +899,22 @@ mod tests {
     fn central_directory_header_version_made_by() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -446,11 +926,22 @@ mod tests {
     fn central_directory_header_version_needed_to_extract() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -462,11 +953,22 @@ mod tests {
     fn central_directory_header_general_purpose_bit_flag() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -478,11 +980,22 @@ mod tests {
     fn central_directory_header_compression_method() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -490,15 +1003,82 @@ mod tests {
         assert_eq!([0x00, 0x00], &central_directory_header[10..12]);
     }
 
+    #[test]
+    fn central_directory_header_compression_method_with_deflate() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 9,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Ba"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x14, 0x00], &central_directory_header[6..8]);
+        assert_eq!([0x08, 0x00], &central_directory_header[10..12]);
+    }
+
+    #[test]
+    fn central_directory_header_sizes_with_deflate() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 900,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Ba"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x03, 0x00, 0x00, 0x00], &central_directory_header[20..24]);
+        assert_eq!([0x84, 0x03, 0x00, 0x00], &central_directory_header[24..28]);
+    }
+
     #[test]
     fn central_directory_header_modified_time() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0xFF84,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -510,11 +1090,22 @@ mod tests {
     fn central_directory_header_modified_date() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0xFEEB,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -526,11 +1117,22 @@ mod tests {
     fn central_directory_header_crc() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0xBEAD1234,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -542,11 +1144,22 @@ mod tests {
     fn central_directory_header_compressed_size() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Ba"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -558,11 +1171,22 @@ mod tests {
     fn central_directory_header_uncompressed_size() {
         let file_entry = ZipFileEntry {
             body: vec![0;5],
+            compressed_size: 5,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam Cat"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -574,11 +1198,22 @@ mod tests {
     fn central_directory_header_file_name_length() {
         let file_entry = ZipFileEntry {
             body: vec![0;5],
+            compressed_size: 5,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam Cat"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -590,11 +1225,22 @@ mod tests {
     fn central_directory_header_extra_field_length() {
         let file_entry = ZipFileEntry {
             body: vec![0;5],
+            compressed_size: 5,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam Cat"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -606,11 +1252,22 @@ mod tests {
     fn central_directory_header_file_comment_length() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -622,11 +1279,22 @@ mod tests {
     fn central_directory_header_disk_number_start() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -638,11 +1306,22 @@ mod tests {
     fn central_directory_header_internal_file_attributes() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -654,11 +1333,22 @@ mod tests {
     fn central_directory_header_external_file_attributes() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -670,11 +1360,22 @@ mod tests {
     fn central_directory_header_relative_offset() {
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: String::from("Foam"),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0x7712AB32,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
@@ -688,15 +1389,599 @@ mod tests {
 
         let file_entry = ZipFileEntry {
             body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
             crc: 0,
             file_name: given_name.clone(),
             dos_time: 0,
             dos_date: 0,
             entry_offset: 0x7712AB32,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
         };
 
         let central_directory_header = file_entry.get_central_directory_header();
 
         assert_eq!(given_name.into_bytes(), &central_directory_header[46..central_directory_header.len()]);
     }
+
+    #[test]
+    fn local_file_header_with_zip64_uncompressed_size() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 0x1_0000_0000,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!(55, file_entry.get_local_file_header_size());
+        assert_eq!(55, local_file_header.len());
+        assert_eq!([0x2D, 0x00], &local_file_header[4..6]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &local_file_header[18..22]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &local_file_header[22..26]);
+        assert_eq!([0x14, 0x00], &local_file_header[28..30]);
+        assert_eq!([0x01, 0x00, 0x10, 0x00], &local_file_header[35..39]);
+        assert_eq!([0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00], &local_file_header[39..47]);
+        assert_eq!([0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &local_file_header[47..55]);
+    }
+
+    #[test]
+    fn local_file_header_with_forced_zip64() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: true,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x2D, 0x00], &local_file_header[4..6]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &local_file_header[18..22]);
+        assert_eq!([0x14, 0x00], &local_file_header[28..30]);
+        assert_eq!([0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &local_file_header[39..47]);
+    }
+
+    #[test]
+    fn central_directory_header_with_zip64_offset() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0x1_2345_6789,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!(62, file_entry.get_central_directory_header_size());
+        assert_eq!(62, central_directory_header.len());
+        assert_eq!([0x2D, 0x00], &central_directory_header[6..8]);
+        assert_eq!([0x03, 0x00, 0x00, 0x00], &central_directory_header[20..24]);
+        assert_eq!([0x0C, 0x00], &central_directory_header[30..32]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &central_directory_header[42..46]);
+        assert_eq!([0x01, 0x00, 0x08, 0x00], &central_directory_header[50..54]);
+        assert_eq!([0x89, 0x67, 0x45, 0x23, 0x01, 0x00, 0x00, 0x00], &central_directory_header[54..62]);
+    }
+
+    #[test]
+    fn central_directory_header_with_forced_zip64() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0x20,
+            force_zip64: true,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!(78, central_directory_header.len());
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &central_directory_header[20..24]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &central_directory_header[24..28]);
+        assert_eq!([0x1C, 0x00], &central_directory_header[30..32]);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0xFF], &central_directory_header[42..46]);
+        assert_eq!([0x01, 0x00, 0x18, 0x00], &central_directory_header[50..54]);
+        assert_eq!([0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &central_directory_header[70..78]);
+    }
+
+    #[test]
+    fn local_file_header_general_purpose_bit_flag_with_unicode_file_name() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("猫/カプー.txt"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x00, 0x08], &local_file_header[6..8]);
+    }
+
+    #[test]
+    fn central_directory_header_general_purpose_bit_flag_with_unicode_file_name() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("🐛🐱.png"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x00, 0x08], &central_directory_header[8..10]);
+    }
+
+    #[test]
+    fn local_file_header_extra_fields() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Capoo"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: vec![
+                ExtraField { header_id: 0x1234, data: vec![0xAA, 0xBB] },
+            ],
+            central_directory_extra_fields: vec![
+                ExtraField { header_id: 0x5678, data: vec![0xCC] },
+            ],
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!(41, file_entry.get_local_file_header_size());
+        assert_eq!([0x06, 0x00], &local_file_header[28..30]);
+        assert_eq!([0x34, 0x12, 0x02, 0x00, 0xAA, 0xBB], &local_file_header[35..41]);
+    }
+
+    #[test]
+    fn central_directory_header_extra_fields_follow_zip64_extra_field() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0x1_0000_0000,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: vec![
+                ExtraField { header_id: 0x1234, data: vec![0xAA, 0xBB] },
+            ],
+            central_directory_extra_fields: vec![
+                ExtraField { header_id: 0x5678, data: vec![0xCC] },
+            ],
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!(67, file_entry.get_central_directory_header_size());
+        assert_eq!([0x11, 0x00], &central_directory_header[30..32]);
+        assert_eq!([0x01, 0x00, 0x08, 0x00], &central_directory_header[50..54]);
+        assert_eq!([0x78, 0x56, 0x01, 0x00, 0xCC], &central_directory_header[62..67]);
+    }
+
+    #[test]
+    fn central_directory_header_external_file_attributes_for_directory() {
+        let file_entry = ZipFileEntry {
+            body: vec![],
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Logs/"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0x10,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x10, 0x00, 0x00, 0x00], &central_directory_header[38..42]);
+    }
+
+    #[test]
+    fn local_file_header_minimum_version_for_directory() {
+        let file_entry = ZipFileEntry {
+            body: vec![],
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Logs/"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0x10,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x14, 0x00], &local_file_header[4..6]);
+    }
+
+    #[test]
+    fn central_directory_header_version_made_by_unix() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("run.sh"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0o100755 << 16,
+            version_made_by: 0x033F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x3F, 0x03], &central_directory_header[4..6]);
+        assert_eq!([0x00, 0x00, 0xED, 0x81], &central_directory_header[38..42]);
+    }
+
+    #[test]
+    fn central_directory_header_with_file_comment() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::from("Capoo"),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!(55, file_entry.get_central_directory_header_size());
+        assert_eq!(55, central_directory_header.len());
+        assert_eq!([0x05, 0x00], &central_directory_header[32..34]);
+        assert_eq!("Capoo".as_bytes(), &central_directory_header[50..55]);
+    }
+
+    #[test]
+    fn general_purpose_bit_flag_with_unicode_file_comment() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 3,
+            compression_method: CompressionMethod::Stored,
+            crc: 0,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::from("🐛🐱"),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x00, 0x08], &local_file_header[6..8]);
+        assert_eq!([0x00, 0x08], &central_directory_header[8..10]);
+    }
+
+    #[test]
+    fn local_file_header_with_data_descriptor() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: true,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+
+        assert_eq!([0x08, 0x00], &local_file_header[6..8]);
+        assert_eq!([0x00; 12], &local_file_header[14..26]);
+    }
+
+    #[test]
+    fn central_directory_header_with_data_descriptor() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: true,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let central_directory_header = file_entry.get_central_directory_header();
+
+        assert_eq!([0x08, 0x00], &central_directory_header[8..10]);
+        assert_eq!([0x78, 0x56, 0x34, 0x12], &central_directory_header[16..20]);
+        assert_eq!([0x03, 0x00, 0x00, 0x00], &central_directory_header[20..24]);
+        assert_eq!([0x05, 0x00, 0x00, 0x00], &central_directory_header[24..28]);
+    }
+
+    #[test]
+    fn data_descriptor_with_signature() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: true,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let data_descriptor = file_entry.get_data_descriptor();
+
+        assert_eq!(vec![
+            0x50, 0x4B, 0x07, 0x08,
+            0x78, 0x56, 0x34, 0x12,
+            0x03, 0x00, 0x00, 0x00,
+            0x05, 0x00, 0x00, 0x00,
+        ], data_descriptor);
+    }
+
+    #[test]
+    fn data_descriptor_without_signature() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: true,
+            data_descriptor_signature: false,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let data_descriptor = file_entry.get_data_descriptor();
+
+        assert_eq!(vec![
+            0x78, 0x56, 0x34, 0x12,
+            0x03, 0x00, 0x00, 0x00,
+            0x05, 0x00, 0x00, 0x00,
+        ], data_descriptor);
+    }
+
+    #[test]
+    fn data_descriptor_with_forced_zip64() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: true,
+            data_descriptor: true,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        let local_file_header = file_entry.get_local_file_header();
+        let data_descriptor = file_entry.get_data_descriptor();
+
+        assert_eq!([0xFF; 8], &local_file_header[18..26]);
+        assert_eq!([0x01, 0x00, 0x10, 0x00], &local_file_header[34..38]);
+        assert_eq!([0x00; 16], &local_file_header[38..54]);
+        assert_eq!(24, data_descriptor.len());
+        assert_eq!([0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &data_descriptor[8..16]);
+        assert_eq!([0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &data_descriptor[16..24]);
+    }
+
+    #[test]
+    fn data_descriptor_without_data_descriptor_mode() {
+        let file_entry = ZipFileEntry {
+            body: vec![0;3],
+            compressed_size: 3,
+            uncompressed_size: 5,
+            compression_method: CompressionMethod::Deflate,
+            crc: 0x12345678,
+            file_name: String::from("Foam"),
+            dos_time: 0,
+            dos_date: 0,
+            entry_offset: 0,
+            force_zip64: false,
+            data_descriptor: false,
+            data_descriptor_signature: true,
+            local_extra_fields: Vec::new(),
+            central_directory_extra_fields: Vec::new(),
+            external_file_attributes: 0,
+            version_made_by: 0x003F,
+            comment: String::new(),
+        };
+
+        assert_eq!(0, file_entry.get_data_descriptor_size());
+    }
 }
\ No newline at end of file
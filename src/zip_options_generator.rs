@@ -0,0 +1,201 @@
+use js_sys::{Array, Date, Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::compressor::MAXIMUM_COMPRESSION_LEVEL;
+use crate::zip_error::ZipError;
+use crate::zip_file::extra_field::ExtraField;
+use crate::zip_file::{CompressionMethod, EntryType, ZipOptions};
+
+pub fn generate_zip_options(options: &Object) -> Result<ZipOptions, ZipError> {
+    let default_options = ZipOptions::default();
+
+    Ok(ZipOptions {
+        compression_method: get_compression_method(options)?.unwrap_or(default_options.compression_method),
+        compression_level: get_compression_level(options).unwrap_or(default_options.compression_level),
+        skip_compression: get_skip_compression(options).unwrap_or(default_options.skip_compression),
+        force_zip64: get_boolean_option(options, "forceZip64").unwrap_or(default_options.force_zip64),
+        data_descriptor: get_boolean_option(options, "dataDescriptor").unwrap_or(default_options.data_descriptor),
+        data_descriptor_signature: get_boolean_option(options, "dataDescriptorSignature").unwrap_or(default_options.data_descriptor_signature),
+        unicode_path_extra_field: get_boolean_option(options, "unicodePathExtraField").unwrap_or(default_options.unicode_path_extra_field),
+        directory_entries: get_boolean_option(options, "directoryEntries").unwrap_or(default_options.directory_entries),
+        timestamp: get_timestamp(options).or(default_options.timestamp),
+        extended_timestamp_extra_field: get_boolean_option(options, "extendedTimestamp").unwrap_or(default_options.extended_timestamp_extra_field),
+        ntfs_extra_field: get_boolean_option(options, "ntfsTimestamp").unwrap_or(default_options.ntfs_extra_field),
+        unix_permissions: get_boolean_option(options, "unixPermissions").unwrap_or(default_options.unix_permissions),
+        comment: get_option(options, "comment").as_string().unwrap_or(default_options.comment),
+    })
+}
+
+pub fn get_compression_method(options: &JsValue) -> Result<Option<CompressionMethod>, ZipError> {
+    let compression = get_option(options, "compression");
+
+    if compression.is_undefined() || compression.is_null() {
+        return Ok(None);
+    }
+
+    match compression.as_string().as_deref() {
+        Some("deflate") => Ok(Some(CompressionMethod::Deflate)),
+        Some("store") | Some("stored") => Ok(Some(CompressionMethod::Stored)),
+        Some(compression_name) => Err(ZipError::InvalidCompressionMethod { compression: compression_name.to_string() }),
+        None => Err(ZipError::InvalidCompressionMethod { compression: format!("{:?}", compression) }),
+    }
+}
+
+pub fn get_compression_level(options: &JsValue) -> Option<u8> {
+    let level = get_option(options, "level").as_f64()?;
+
+    Some(level.clamp(0.0, MAXIMUM_COMPRESSION_LEVEL as f64) as u8)
+}
+
+pub fn get_mime_type(options: &JsValue) -> Option<String> {
+    get_option(options, "type").as_string()
+}
+
+pub fn get_comment(options: &JsValue) -> Option<String> {
+    get_option(options, "comment").as_string()
+}
+
+pub fn get_entry_type(options: &JsValue) -> EntryType {
+    if get_boolean_option(options, "directory") == Some(true) {
+        return EntryType::Directory;
+    }
+
+    if get_boolean_option(options, "symlink") == Some(true) {
+        return EntryType::Symlink;
+    }
+
+    EntryType::File
+}
+
+pub fn get_last_modified(options: &JsValue) -> Option<i64> {
+    get_unix_time_millis(&get_option(options, "lastModified"))
+}
+
+pub fn get_last_accessed(options: &JsValue) -> Option<i64> {
+    get_unix_time_millis(&get_option(options, "lastAccessed"))
+}
+
+pub fn get_created(options: &JsValue) -> Option<i64> {
+    get_unix_time_millis(&get_option(options, "created"))
+}
+
+pub fn get_unix_mode(options: &JsValue) -> Option<u32> {
+    get_unsigned_integer_option(options, "mode")
+}
+
+pub fn get_uid(options: &JsValue) -> Option<u32> {
+    get_unsigned_integer_option(options, "uid")
+}
+
+pub fn get_gid(options: &JsValue) -> Option<u32> {
+    get_unsigned_integer_option(options, "gid")
+}
+
+pub fn get_local_extra_fields(options: &JsValue, file_name: &str) -> Result<Vec<ExtraField>, ZipError> {
+    get_extra_fields(options, "localExtraFields", file_name)
+}
+
+pub fn get_central_directory_extra_fields(options: &JsValue, file_name: &str) -> Result<Vec<ExtraField>, ZipError> {
+    get_extra_fields(options, "centralExtraFields", file_name)
+}
+
+//Each extra field is an object with a numeric header id and its data as a Uint8Array
+fn get_extra_fields(options: &JsValue, option_name: &str, file_name: &str) -> Result<Vec<ExtraField>, ZipError> {
+    let extra_fields = get_option(options, option_name);
+
+    if extra_fields.is_undefined() || extra_fields.is_null() {
+        return Ok(Vec::new());
+    }
+
+    if !Array::is_array(&extra_fields) {
+        return Err(ZipError::InvalidExtraField { file_name: file_name.to_string() });
+    }
+
+    Array::from(&extra_fields)
+        .iter()
+        .map(|extra_field| get_extra_field(&extra_field).ok_or_else(|| ZipError::InvalidExtraField { file_name: file_name.to_string() }))
+        .collect()
+}
+
+fn get_extra_field(extra_field: &JsValue) -> Option<ExtraField> {
+    let header_id = get_unsigned_integer_option(extra_field, "id")?;
+    let data = get_option(extra_field, "data").dyn_into::<Uint8Array>().ok()?;
+
+    if header_id > u16::MAX as u32 || data.length() > u16::MAX as u32 {
+        return None;
+    }
+
+    Some(ExtraField {
+        header_id: header_id as u16,
+        data: data.to_vec(),
+    })
+}
+
+fn get_timestamp(options: &JsValue) -> Option<i64> {
+    get_unix_time_millis(&get_option(options, "timestamp"))
+        .or_else(|| get_source_date_epoch(&get_option(options, "sourceDateEpoch")))
+}
+
+//Accepts either a JS Date or a number of milliseconds since the Unix epoch
+fn get_unix_time_millis(timestamp: &JsValue) -> Option<i64> {
+    let milliseconds = match timestamp.dyn_ref::<Date>() {
+        Some(date) => date.get_time(),
+        None => timestamp.as_f64()?,
+    };
+
+    if !milliseconds.is_finite() {
+        return None;
+    }
+
+    Some(milliseconds.floor() as i64)
+}
+
+//Accepts a number or numeric string of seconds since the Unix epoch, as found in the SOURCE_DATE_EPOCH variable
+fn get_source_date_epoch(source_date_epoch: &JsValue) -> Option<i64> {
+    if let Some(seconds) = source_date_epoch.as_string() {
+        let seconds: i64 = seconds.trim().parse().ok()?;
+
+        return Some(seconds.saturating_mul(1000));
+    }
+
+    let seconds = source_date_epoch.as_f64()?;
+
+    if !seconds.is_finite() {
+        return None;
+    }
+
+    Some((seconds.floor() * 1000.0) as i64)
+}
+
+fn get_skip_compression(options: &JsValue) -> Option<Vec<String>> {
+    let skip_compression = get_option(options, "skipCompression");
+
+    if !Array::is_array(&skip_compression) {
+        return None;
+    }
+
+    let patterns = Array::from(&skip_compression)
+        .iter()
+        .filter_map(|pattern| pattern.as_string())
+        .collect();
+
+    Some(patterns)
+}
+
+fn get_boolean_option(options: &JsValue, option_name: &str) -> Option<bool> {
+    get_option(options, option_name).as_bool()
+}
+
+fn get_unsigned_integer_option(options: &JsValue, option_name: &str) -> Option<u32> {
+    let value = get_option(options, option_name).as_f64()?;
+
+    if !value.is_finite() || value < 0.0 || value > u32::MAX as f64 {
+        return None;
+    }
+
+    Some(value as u32)
+}
+
+fn get_option(options: &JsValue, option_name: &str) -> JsValue {
+    Reflect::get(options, &JsValue::from(option_name)).unwrap_or(JsValue::UNDEFINED)
+}
\ No newline at end of file
@@ -0,0 +1,444 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use crc::{Crc, Digest, CRC_32_ISO_HDLC};
+
+use crate::compressor::deflate_compressor_adapter::DeflateCompressorAdapter;
+use crate::compressor::deflate_stream_compressor_adapter::DeflateStreamCompressorAdapter;
+use crate::compressor::StreamCompressor;
+use crate::crc_calculator::crc_calculator_adapter::CrcCalculatorAdapter;
+use crate::date_time_converter::dos_date_time_calculator_adapter::DosDateTimeCalculatorAdapter;
+use crate::date_time_converter::{get_current_unix_time_millis, SystemTime};
+use crate::zip_error::ZipError;
+use crate::zip_file::zip_blob_factory::{CentralDirectorySummary, ZipBlobFactoryAdapter};
+use crate::zip_file::zip_file_entry::ZipFileEntry;
+use crate::zip_file::{CompressionMethod, EntryType, FileDescriptor, ZipOptions, ZIP64_FIELD_LIMIT};
+
+static CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+//Without a data descriptor, each file is held in memory until the next one is started, as its crc and size are needed
+//for the local file header. With one, the header is written straight away and the body is compressed as it arrives
+pub struct ZipWriter<W: Write> {
+    writer: W,
+    zip_blob_factory: ZipBlobFactoryAdapter,
+    current_file: Option<CurrentFile>,
+    directory_names: HashSet<String>,
+    central_directory_headers: Vec<u8>,
+    number_of_entries: u64,
+    offset: u64,
+}
+
+enum CurrentFile {
+    Buffered(String, FileDescriptor),
+    Streamed(StreamedFile),
+}
+
+struct StreamedFile {
+    zip_entry: ZipFileEntry,
+    crc_digest: Digest<'static, u32>,
+    stream_compressor: Option<Box<dyn StreamCompressor>>,
+}
+
+impl<W: Write> ZipWriter<W> {
+    pub fn new(writer: W) -> ZipWriter<W> {
+        ZipWriter::with_options(writer, ZipOptions::default())
+    }
+
+    //Entries are stamped in UTC, as the local time zone is only known to the browser
+    pub fn with_options(writer: W, options: ZipOptions) -> ZipWriter<W> {
+        let unix_time_millis = options.timestamp.unwrap_or_else(get_current_unix_time_millis);
+
+        let zip_blob_factory = ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(CrcCalculatorAdapter {
+                crc: Crc::<u32>::new(&CRC_32_ISO_HDLC)
+            }),
+            date_time_retriever: Box::new(DosDateTimeCalculatorAdapter {
+                date_time: SystemTime::from_unix_time_millis(unix_time_millis),
+                unix_time_millis,
+                to_date_time: SystemTime::from_unix_time_millis,
+            }),
+            compressor: Box::new(DeflateCompressorAdapter {}),
+            options,
+        };
+
+        ZipWriter {
+            writer,
+            zip_blob_factory,
+            current_file: None,
+            directory_names: HashSet::new(),
+            central_directory_headers: Vec::new(),
+            number_of_entries: 0,
+            offset: 0,
+        }
+    }
+
+    //The body of the descriptor is the start of the file, and anything written afterwards is appended to it
+    pub fn start_file(&mut self, file_name: &str, mut file_descriptor: FileDescriptor) -> Result<(), ZipError> {
+        self.finish_file()?;
+
+        if !self.zip_blob_factory.options.data_descriptor {
+            self.current_file = Some(CurrentFile::Buffered(file_name.to_string(), file_descriptor));
+
+            return Ok(());
+        }
+
+        let file_body = std::mem::take(&mut file_descriptor.body);
+        let entries = self.zip_blob_factory.get_entries_with_directories(file_name.to_string(), file_descriptor, &mut self.directory_names);
+
+        for (entry_name, entry_descriptor) in entries {
+            match entry_descriptor.entry_type {
+                EntryType::Directory => self.write_entry(entry_name, entry_descriptor)?,
+                _ => self.start_streamed_entry(entry_name, entry_descriptor)?,
+            }
+        }
+
+        if let Some(CurrentFile::Streamed(streamed_file)) = &mut self.current_file {
+            write_streamed_chunk(&mut self.writer, streamed_file, &file_body)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, ZipError> {
+        self.zip_blob_factory.validate_archive_comment()?;
+        self.finish_file()?;
+
+        let central_directory_size = self.central_directory_headers.len() as u64;
+
+        self.offset
+            .checked_add(central_directory_size)
+            .ok_or(ZipError::OffsetOverflow)?;
+
+        let central_directory_summary = CentralDirectorySummary {
+            number_of_records: self.number_of_entries,
+            size: central_directory_size,
+            start_offset: self.offset,
+        };
+
+        let end_of_central_directory_records = self.zip_blob_factory.get_end_of_central_directory_records(&central_directory_summary, self.zip_blob_factory.options.comment.as_bytes());
+
+        self.writer.write_all(&self.central_directory_headers)?;
+        self.writer.write_all(&end_of_central_directory_records)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn finish_file(&mut self) -> Result<(), ZipError> {
+        match self.current_file.take() {
+            Some(CurrentFile::Buffered(file_name, file_descriptor)) => {
+                let entries = self.zip_blob_factory.get_entries_with_directories(file_name, file_descriptor, &mut self.directory_names);
+
+                for (entry_name, entry_descriptor) in entries {
+                    self.write_entry(entry_name, entry_descriptor)?;
+                }
+
+                Ok(())
+            }
+            Some(CurrentFile::Streamed(streamed_file)) => self.finish_streamed_entry(streamed_file),
+            None => Ok(()),
+        }
+    }
+
+    fn write_entry(&mut self, file_name: String, file_descriptor: FileDescriptor) -> Result<(), ZipError> {
+        let zip_entry = self.zip_blob_factory.create_validated_zip_file_entry(file_name, file_descriptor, self.offset)?;
+
+        let next_offset = self.offset
+            .checked_add(self.zip_blob_factory.get_zip_file_size(&zip_entry))
+            .ok_or(ZipError::OffsetOverflow)?;
+
+        self.writer.write_all(&zip_entry.get_local_file_header())?;
+        self.writer.write_all(&zip_entry.body)?;
+        self.writer.write_all(&zip_entry.get_data_descriptor())?;
+
+        self.add_central_directory_header(&zip_entry);
+        self.offset = next_offset;
+
+        Ok(())
+    }
+
+    fn start_streamed_entry(&mut self, file_name: String, file_descriptor: FileDescriptor) -> Result<(), ZipError> {
+        let (zip_entry, compression_level) = self.zip_blob_factory.create_validated_streamed_zip_file_entry(file_name, file_descriptor, self.offset)?;
+
+        self.writer.write_all(&zip_entry.get_local_file_header())?;
+
+        let stream_compressor: Option<Box<dyn StreamCompressor>> = match zip_entry.compression_method {
+            CompressionMethod::Deflate => Some(Box::new(DeflateStreamCompressorAdapter::new(compression_level))),
+            CompressionMethod::Stored => None,
+        };
+
+        self.current_file = Some(CurrentFile::Streamed(StreamedFile {
+            zip_entry,
+            crc_digest: CRC_32.digest(),
+            stream_compressor,
+        }));
+
+        Ok(())
+    }
+
+    //Without ZIP64 in the local header, the data descriptor can only hold 32-bit sizes
+    fn finish_streamed_entry(&mut self, mut streamed_file: StreamedFile) -> Result<(), ZipError> {
+        if let Some(stream_compressor) = &mut streamed_file.stream_compressor {
+            let compressed_chunk = stream_compressor.finish();
+
+            self.writer.write_all(&compressed_chunk)?;
+            streamed_file.zip_entry.compressed_size += compressed_chunk.len() as u64;
+        }
+
+        let mut zip_entry = streamed_file.zip_entry;
+        zip_entry.crc = streamed_file.crc_digest.finalize();
+
+        if !zip_entry.force_zip64 && (zip_entry.uncompressed_size >= ZIP64_FIELD_LIMIT || zip_entry.compressed_size >= ZIP64_FIELD_LIMIT) {
+            return Err(ZipError::StreamedFileTooLarge { file_name: zip_entry.file_name });
+        }
+
+        let next_offset = self.offset
+            .checked_add(self.zip_blob_factory.get_zip_file_size(&zip_entry))
+            .and_then(|next_offset| next_offset.checked_add(zip_entry.compressed_size))
+            .ok_or(ZipError::OffsetOverflow)?;
+
+        self.writer.write_all(&zip_entry.get_data_descriptor())?;
+
+        self.add_central_directory_header(&zip_entry);
+        self.offset = next_offset;
+
+        Ok(())
+    }
+
+    fn add_central_directory_header(&mut self, zip_entry: &ZipFileEntry) {
+        let mut central_directory_header = zip_entry.get_central_directory_header();
+        self.central_directory_headers.append(&mut central_directory_header);
+
+        self.number_of_entries += 1;
+    }
+}
+
+fn write_streamed_chunk<W: Write>(writer: &mut W, streamed_file: &mut StreamedFile, chunk: &[u8]) -> io::Result<()> {
+    streamed_file.crc_digest.update(chunk);
+    streamed_file.zip_entry.uncompressed_size += chunk.len() as u64;
+
+    match &mut streamed_file.stream_compressor {
+        Some(stream_compressor) => {
+            let compressed_chunk = stream_compressor.compress_chunk(chunk);
+
+            writer.write_all(&compressed_chunk)?;
+            streamed_file.zip_entry.compressed_size += compressed_chunk.len() as u64;
+        }
+        None => {
+            writer.write_all(chunk)?;
+            streamed_file.zip_entry.compressed_size += chunk.len() as u64;
+        }
+    }
+
+    Ok(())
+}
+
+impl<W: Write> Write for ZipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.current_file {
+            Some(CurrentFile::Buffered(_, file_descriptor)) => {
+                file_descriptor.body.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            Some(CurrentFile::Streamed(streamed_file)) => {
+                write_streamed_chunk(&mut self.writer, streamed_file, buf)?;
+                Ok(buf.len())
+            }
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "A file must be started before it is written")),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zip_file::ZipBlobFactory;
+
+    const TIMESTAMP: i64 = 1654424430000;
+
+    fn create_zip_blob_factory(options: ZipOptions) -> ZipBlobFactoryAdapter {
+        ZipBlobFactoryAdapter {
+            crc_calculator: Box::new(CrcCalculatorAdapter {
+                crc: Crc::<u32>::new(&CRC_32_ISO_HDLC)
+            }),
+            date_time_retriever: Box::new(DosDateTimeCalculatorAdapter {
+                date_time: SystemTime::from_unix_time_millis(TIMESTAMP),
+                unix_time_millis: TIMESTAMP,
+                to_date_time: SystemTime::from_unix_time_millis,
+            }),
+            compressor: Box::new(DeflateCompressorAdapter {}),
+            options,
+        }
+    }
+
+    #[test]
+    fn zip_writer_should_match_zip_blob_factory() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+
+        zip_writer.start_file("Capoo.txt", FileDescriptor::default()).unwrap();
+        zip_writer.write_all(b"Capoo is Hungry. ").unwrap();
+        zip_writer.write_all(b"Capoo is Hungry.").unwrap();
+        zip_writer.start_file("Folder/BugCat.bin", FileDescriptor {
+            body: vec![1, 2, 3],
+            compression_method: Some(CompressionMethod::Stored),
+            ..FileDescriptor::default()
+        }).unwrap();
+
+        let zip_binary = zip_writer.finish().unwrap();
+
+        let zip_blob_factory = create_zip_blob_factory(ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+        let expected_zip_binary = zip_blob_factory.create_zip_binary(vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: b"Capoo is Hungry. Capoo is Hungry.".to_vec(),
+                ..FileDescriptor::default()
+            }),
+            (String::from("Folder/BugCat.bin"), FileDescriptor {
+                body: vec![1, 2, 3],
+                compression_method: Some(CompressionMethod::Stored),
+                ..FileDescriptor::default()
+            }),
+        ]).unwrap();
+
+        assert_eq!(expected_zip_binary.to_vec(), zip_binary);
+    }
+
+    #[test]
+    fn zip_writer_should_match_zip_blob_factory_with_zip64_and_data_descriptors() {
+        let options = || ZipOptions { timestamp: Some(TIMESTAMP), force_zip64: true, data_descriptor: true, ..ZipOptions::default() };
+
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), options());
+
+        zip_writer.start_file("Capoo.txt", FileDescriptor { compression_method: Some(CompressionMethod::Stored), ..FileDescriptor::default() }).unwrap();
+        zip_writer.write_all(b"Capoo is Hungry.").unwrap();
+        zip_writer.start_file("Folder/BugCat.txt", FileDescriptor { body: b"BugCat is ".to_vec(), ..FileDescriptor::default() }).unwrap();
+
+        for _ in 0..1000 {
+            zip_writer.write_all(b"Capoo is Hungry. ").unwrap();
+        }
+
+        let zip_binary = zip_writer.finish().unwrap();
+
+        let zip_blob_factory = create_zip_blob_factory(options());
+        let expected_zip_binary = zip_blob_factory.create_zip_binary(vec![
+            (String::from("Capoo.txt"), FileDescriptor {
+                body: b"Capoo is Hungry.".to_vec(),
+                compression_method: Some(CompressionMethod::Stored),
+                ..FileDescriptor::default()
+            }),
+            (String::from("Folder/BugCat.txt"), FileDescriptor {
+                body: format!("BugCat is {}", "Capoo is Hungry. ".repeat(1000)).into_bytes(),
+                ..FileDescriptor::default()
+            }),
+        ]).unwrap();
+
+        assert_eq!(expected_zip_binary.to_vec(), zip_binary);
+    }
+
+    #[test]
+    fn zip_writer_should_stream_files_with_data_descriptors() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), data_descriptor: true, ..ZipOptions::default() });
+
+        zip_writer.start_file("Capoo.txt", FileDescriptor { compression_method: Some(CompressionMethod::Stored), ..FileDescriptor::default() }).unwrap();
+        assert_eq!(39, zip_writer.writer.len());
+
+        zip_writer.write_all(b"Capoo is Hungry.").unwrap();
+        assert_eq!(b"Capoo is Hungry.", &zip_writer.writer[39..]);
+
+        zip_writer.start_file("BugCat.txt", FileDescriptor::default()).unwrap();
+        assert_eq!([0x50, 0x4B, 0x07, 0x08], &zip_writer.writer[55..59]);
+        assert_eq!([0x50, 0x4B, 0x03, 0x04], &zip_writer.writer[71..75]);
+
+        match &zip_writer.current_file {
+            Some(CurrentFile::Streamed(streamed_file)) => assert!(streamed_file.zip_entry.body.is_empty()),
+            _ => panic!("The file should be streamed"),
+        }
+    }
+
+    #[test]
+    fn zip_writer_should_reject_streamed_files_that_outgrow_their_header() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { data_descriptor: true, ..ZipOptions::default() });
+
+        zip_writer.start_file("FoamCat.mp4", FileDescriptor::default()).unwrap();
+
+        if let Some(CurrentFile::Streamed(streamed_file)) = &mut zip_writer.current_file {
+            streamed_file.zip_entry.uncompressed_size = ZIP64_FIELD_LIMIT;
+        }
+
+        let zip_error = zip_writer.finish().err();
+
+        assert_eq!(Some(ZipError::StreamedFileTooLarge { file_name: String::from("FoamCat.mp4") }), zip_error);
+    }
+
+    #[test]
+    fn zip_writer_should_write_parent_directories_once() {
+        let mut zip_writer = ZipWriter::with_options(Vec::new(), ZipOptions { timestamp: Some(TIMESTAMP), ..ZipOptions::default() });
+
+        zip_writer.start_file("Folder/Capoo.txt", FileDescriptor::default()).unwrap();
+        zip_writer.start_file("Folder", FileDescriptor { entry_type: EntryType::Directory, ..FileDescriptor::default() }).unwrap();
+        zip_writer.start_file("Folder/BugCat.txt", FileDescriptor::default()).unwrap();
+
+        let zip_binary = zip_writer.finish().unwrap();
+
+        assert_eq!([0x03, 0x00], &zip_binary[zip_binary.len() - 12..zip_binary.len() - 10]);
+    }
+
+    #[test]
+    fn empty_zip_writer_should_write_end_of_central_directory() {
+        let zip_binary = ZipWriter::new(Vec::new()).finish().unwrap();
+
+        assert_eq!(vec![
+            0x50, 0x4B, 0x05, 0x06,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ], zip_binary);
+    }
+
+    #[test]
+    fn zip_writer_should_reject_data_before_a_file_is_started() {
+        let mut zip_writer = ZipWriter::new(Vec::new());
+
+        let io_error = zip_writer.write_all(b"Capoo").unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidInput, io_error.kind());
+    }
+
+    #[test]
+    fn zip_writer_should_reject_invalid_files() {
+        let mut zip_writer = ZipWriter::new(Vec::new());
+
+        zip_writer.start_file(&"a".repeat(0x10000), FileDescriptor::default()).unwrap();
+
+        let zip_error = zip_writer.finish().err();
+
+        assert_eq!(Some(ZipError::FileNameTooLong { file_name: "a".repeat(0x10000) }), zip_error);
+    }
+
+    #[test]
+    fn zip_writer_should_return_write_errors() {
+        struct FullWriter {}
+
+        impl Write for FullWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::WriteZero, "disk full"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut zip_writer = ZipWriter::new(FullWriter {});
+        zip_writer.start_file("Capoo.txt", FileDescriptor::default()).unwrap();
+
+        let zip_error = zip_writer.finish().err();
+
+        assert_eq!(Some(ZipError::Io { kind: io::ErrorKind::WriteZero, message: String::from("disk full") }), zip_error);
+    }
+}
\ No newline at end of file
@@ -2,10 +2,13 @@
 
 extern crate wasm_bindgen_test;
 
-use js_sys::{Object, Reflect};
+use js_sys::{Array, Date, Object, Reflect, Uint8Array};
 use wasm_bindgen_test::*;
 use wasm_bindgen::JsValue;
-use wasm_zip::generate_zip_binary;
+use wasm_zip::{
+    append_zip_binary, append_zip_binary_with_options, delete_zip_entries, extract_zip_binary, extract_zip_entry, generate_zip_binary,
+    generate_zip_binary_with_options, list_zip_entries, rename_zip_entries, ZipArchive,
+};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -18,7 +21,7 @@ fn single_file() {
 
     Reflect::set(&directory_object, &file_name, &file_data).unwrap();
 
-    generate_zip_binary(directory_object);
+    assert!(generate_zip_binary(directory_object).is_ok());
 }
 
 #[wasm_bindgen_test]
@@ -36,7 +39,7 @@ fn multiple_files() {
     Reflect::set(&directory_object, &second_file_name, &second_file_data).unwrap();
     Reflect::set(&directory_object, &third_file_name, &third_file_data).unwrap();
 
-    generate_zip_binary(directory_object);
+    assert!(generate_zip_binary(directory_object).is_ok());
 }
 
 #[wasm_bindgen_test]
@@ -54,5 +57,573 @@ fn multiple_files_with_folders() {
     Reflect::set(&directory_object, &second_file_name, &second_file_data).unwrap();
     Reflect::set(&directory_object, &third_file_name, &third_file_data).unwrap();
 
-    generate_zip_binary(directory_object);
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn file_descriptors_with_archive_options() {
+    let directory_object = Object::new();
+
+    let first_file_name = JsValue::from("Photo.jpeg");
+    let first_file_descriptor = Object::new();
+    Reflect::set(&first_file_descriptor, &JsValue::from("data"), &JsValue::from("yow")).unwrap();
+    Reflect::set(&first_file_descriptor, &JsValue::from("compression"), &JsValue::from("store")).unwrap();
+
+    let second_file_name = JsValue::from("Report.json");
+    let second_file_descriptor = Object::new();
+    Reflect::set(&second_file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&second_file_descriptor, &JsValue::from("level"), &JsValue::from(9)).unwrap();
+
+    Reflect::set(&directory_object, &first_file_name, &first_file_descriptor).unwrap();
+    Reflect::set(&directory_object, &second_file_name, &second_file_descriptor).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("compression"), &JsValue::from("deflate")).unwrap();
+    Reflect::set(&options, &JsValue::from("level"), &JsValue::from(1)).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn folders_holding_files_named_like_descriptor_properties() {
+    let directory_object = Object::new();
+    let first_folder_object = Object::new();
+    let second_folder_object = Object::new();
+
+    Reflect::set(&first_folder_object, &JsValue::from("data"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&second_folder_object, &JsValue::from("data"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&second_folder_object, &JsValue::from("notes.txt"), &JsValue::from("Bye!")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("docs"), &first_folder_object).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("more"), &second_folder_object).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+
+    let contains_file_name = |file_name: &[u8]| zip_binary.windows(file_name.len()).any(|window| window == file_name);
+
+    assert!(contains_file_name(b"docs/data"));
+    assert!(contains_file_name(b"more/data"));
+    assert!(contains_file_name(b"more/notes.txt"));
+}
+
+#[wasm_bindgen_test]
+fn unknown_compression_method() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("Report.json");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("compression"), &JsValue::from("deflat")).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_err());
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("compression"), &JsValue::from("zstd")).unwrap();
+
+    assert!(generate_zip_binary_with_options(Object::new(), options).is_err());
+}
+
+#[wasm_bindgen_test]
+fn file_name_too_long() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("a".repeat(65536));
+    let file_data = JsValue::from("Hello!");
+
+    Reflect::set(&directory_object, &file_name, &file_data).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_err());
+}
+
+#[wasm_bindgen_test]
+fn empty_folder() {
+    let directory_object = Object::new();
+
+    let folder_name = JsValue::from("logs");
+    let folder_object = Object::new();
+
+    Reflect::set(&directory_object, &folder_name, &folder_object).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn empty_folder_without_directory_entries() {
+    let directory_object = Object::new();
+
+    let folder_name = JsValue::from("logs");
+    let folder_object = Object::new();
+
+    Reflect::set(&directory_object, &folder_name, &folder_object).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("directoryEntries"), &JsValue::from(false)).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn fixed_timestamp_is_reproducible() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let first_options = Object::new();
+    Reflect::set(&first_options, &JsValue::from("timestamp"), &Date::new(&JsValue::from(1654424430000.0))).unwrap();
+
+    let second_options = Object::new();
+    Reflect::set(&second_options, &JsValue::from("sourceDateEpoch"), &JsValue::from("1654424430")).unwrap();
+
+    let first_zip_binary = generate_zip_binary_with_options(directory_object.clone(), first_options).ok();
+    let second_zip_binary = generate_zip_binary_with_options(directory_object, second_options).ok();
+
+    assert!(first_zip_binary.is_some());
+    assert_eq!(first_zip_binary, second_zip_binary);
+}
+
+#[wasm_bindgen_test]
+fn file_descriptors_with_last_modified() {
+    let directory_object = Object::new();
+
+    let first_file_name = JsValue::from("Photo.jpeg");
+    let first_file_descriptor = Object::new();
+    Reflect::set(&first_file_descriptor, &JsValue::from("data"), &JsValue::from("yow")).unwrap();
+    Reflect::set(&first_file_descriptor, &JsValue::from("lastModified"), &Date::new(&JsValue::from(1654424430000.0))).unwrap();
+
+    let second_file_name = JsValue::from("Report.json");
+    let second_file_descriptor = Object::new();
+    Reflect::set(&second_file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&second_file_descriptor, &JsValue::from("lastModified"), &JsValue::from(1654424430000.0)).unwrap();
+
+    Reflect::set(&directory_object, &first_file_name, &first_file_descriptor).unwrap();
+    Reflect::set(&directory_object, &second_file_name, &second_file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn extended_timestamps() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("Notes.txt");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("lastModified"), &JsValue::from(1654424430000.0)).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("lastAccessed"), &Date::new(&JsValue::from(1654424431000.0))).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("created"), &JsValue::from(0)).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("extendedTimestamp"), &JsValue::from(true)).unwrap();
+    Reflect::set(&options, &JsValue::from("ntfsTimestamp"), &JsValue::from(true)).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn unix_permissions() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("start.sh");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("#!/bin/sh")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("mode"), &JsValue::from(0o755)).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("uid"), &JsValue::from(1000)).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("gid"), &JsValue::from(1000)).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("README.md"), &JsValue::from("Run start.sh")).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("unixPermissions"), &JsValue::from(true)).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn symbolic_link() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("start");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("run.sh")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("symlink"), &JsValue::from(true)).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("run.sh"), &JsValue::from("#!/bin/sh")).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn custom_extra_fields() {
+    let directory_object = Object::new();
+
+    let extra_field = Object::new();
+    Reflect::set(&extra_field, &JsValue::from("id"), &JsValue::from(0xCA7C)).unwrap();
+    Reflect::set(&extra_field, &JsValue::from("data"), &Uint8Array::from(&[1u8, 2, 3][..])).unwrap();
+
+    let file_name = JsValue::from("Report.json");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("localExtraFields"), &Array::of1(&extra_field)).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("centralExtraFields"), &Array::of1(&extra_field)).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn reserved_custom_extra_field() {
+    let directory_object = Object::new();
+
+    let extra_field = Object::new();
+    Reflect::set(&extra_field, &JsValue::from("id"), &JsValue::from(0x0001)).unwrap();
+    Reflect::set(&extra_field, &JsValue::from("data"), &Uint8Array::new_with_length(8)).unwrap();
+
+    let file_name = JsValue::from("Report.json");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("localExtraFields"), &Array::of1(&extra_field)).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_err());
+}
+
+#[wasm_bindgen_test]
+fn malformed_custom_extra_field() {
+    let extra_field = Object::new();
+    Reflect::set(&extra_field, &JsValue::from("id"), &JsValue::from(0xCA7C)).unwrap();
+    Reflect::set(&extra_field, &JsValue::from("data"), &JsValue::from("not bytes")).unwrap();
+
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("centralExtraFields"), &Array::of1(&extra_field)).unwrap();
+
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("Report.json"), &file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_err());
+}
+
+#[wasm_bindgen_test]
+fn archive_comment() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("comment"), &JsValue::from("Exported by Capoo")).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn archive_comment_containing_signature() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("comment"), &JsValue::from("PK\u{5}\u{6}")).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_err());
+}
+
+#[wasm_bindgen_test]
+fn file_comment() {
+    let directory_object = Object::new();
+
+    let file_name = JsValue::from("Report.json");
+    let file_descriptor = Object::new();
+    Reflect::set(&file_descriptor, &JsValue::from("data"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&file_descriptor, &JsValue::from("comment"), &JsValue::from("Monthly report")).unwrap();
+
+    Reflect::set(&directory_object, &file_name, &file_descriptor).unwrap();
+
+    assert!(generate_zip_binary(directory_object).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn data_descriptors() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("dataDescriptor"), &JsValue::from(true)).unwrap();
+    Reflect::set(&options, &JsValue::from("dataDescriptorSignature"), &JsValue::from(false)).unwrap();
+
+    assert!(generate_zip_binary_with_options(directory_object, options).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn list_zip_entries_of_generated_archive() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let zip_entries = list_zip_entries(Uint8Array::from(&zip_binary[..])).ok().unwrap();
+    let zip_entry = zip_entries.get(0);
+
+    assert_eq!(1, zip_entries.length());
+    assert_eq!(Some(String::from("Hello.txt")), Reflect::get(&zip_entry, &JsValue::from("name")).unwrap().as_string());
+    assert_eq!(Some(6.0), Reflect::get(&zip_entry, &JsValue::from("size")).unwrap().as_f64());
+}
+
+#[wasm_bindgen_test]
+fn list_zip_entries_keeps_last_modified() {
+    let last_modified = JsValue::from(1654424430000.0);
+
+    let dos_file_descriptor = Object::new();
+    Reflect::set(&dos_file_descriptor, &JsValue::from("data"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&dos_file_descriptor, &JsValue::from("lastModified"), &last_modified).unwrap();
+
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &dos_file_descriptor).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let zip_entries = list_zip_entries(Uint8Array::from(&zip_binary[..])).ok().unwrap();
+    let listed_last_modified = Reflect::get(&zip_entries.get(0), &JsValue::from("lastModified")).unwrap();
+
+    assert_eq!(1654424430000.0, Date::from(listed_last_modified).get_time());
+    assert_eq!(1654424430000, ZipArchive::new(&zip_binary).unwrap().get_entries()[0].get_last_modified());
+
+    let extended_file_descriptor = Object::new();
+    Reflect::set(&extended_file_descriptor, &JsValue::from("data"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&extended_file_descriptor, &JsValue::from("lastModified"), &last_modified).unwrap();
+
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &extended_file_descriptor).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("extendedTimestamp"), &JsValue::from(true)).unwrap();
+    Reflect::set(&options, &JsValue::from("timestamp"), &JsValue::from(0)).unwrap();
+
+    let zip_binary = generate_zip_binary_with_options(directory_object, options).ok().unwrap();
+    let zip_entries = list_zip_entries(Uint8Array::from(&zip_binary[..])).ok().unwrap();
+    let listed_last_modified = Reflect::get(&zip_entries.get(0), &JsValue::from("lastModified")).unwrap();
+
+    assert_eq!(1654424430000.0, Date::from(listed_last_modified).get_time());
+}
+
+#[wasm_bindgen_test]
+fn list_zip_entries_of_invalid_archive() {
+    assert!(list_zip_entries(Uint8Array::from(&b"Hello!"[..])).is_err());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_binary_of_generated_archive() {
+    let directory_object = Object::new();
+    let folder_object = Object::new();
+
+    Reflect::set(&folder_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("MyFolder"), &folder_object).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("Empty"), &Object::new()).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let extracted_object = extract_zip_binary(Uint8Array::from(&zip_binary[..])).ok().unwrap();
+
+    let extracted_folder = Reflect::get(&extracted_object, &JsValue::from("MyFolder")).unwrap();
+    let extracted_file = Uint8Array::new(&Reflect::get(&extracted_folder, &JsValue::from("Hello.txt")).unwrap());
+
+    assert_eq!(b"Hello!".to_vec(), extracted_file.to_vec());
+    assert!(Reflect::get(&extracted_object, &JsValue::from("Empty")).unwrap().is_object());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_binary_of_corrupted_archive() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let mut zip_binary = generate_zip_binary(directory_object).ok().unwrap().to_vec();
+    zip_binary[39] ^= 0xFF;
+
+    assert!(extract_zip_binary(Uint8Array::from(&zip_binary[..])).is_err());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_binary_with_prototype_path() {
+    let folder_object = Object::new();
+    Reflect::set(&folder_object, &JsValue::from("x"), &JsValue::from("polluted")).unwrap();
+
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("aaaaaaaaa"), &folder_object).unwrap();
+
+    //The folder name has the same length as __proto__, so it can be replaced in place in both headers
+    let mut zip_binary = generate_zip_binary(directory_object).ok().unwrap().into_vec();
+    replace_all_bytes(&mut zip_binary, b"aaaaaaaaa", b"__proto__");
+
+    assert!(extract_zip_binary(Uint8Array::from(&zip_binary[..])).is_err());
+    assert!(Reflect::get(&Object::new(), &JsValue::from("x")).unwrap().is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_binary_with_file_and_folder_of_the_same_name() {
+    let folder_object = Object::new();
+    Reflect::set(&folder_object, &JsValue::from("a.txt"), &JsValue::from("Hello!")).unwrap();
+
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("Docs"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("Doxs"), &folder_object).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("directoryEntries"), &JsValue::from(false)).unwrap();
+
+    let mut zip_binary = generate_zip_binary_with_options(directory_object, options).ok().unwrap().into_vec();
+    replace_all_bytes(&mut zip_binary, b"Doxs/", b"Docs/");
+
+    assert!(extract_zip_binary(Uint8Array::from(&zip_binary[..])).is_err());
+}
+
+fn replace_all_bytes(zip_binary: &mut [u8], pattern: &[u8], replacement: &[u8]) {
+    for offset in 0..=zip_binary.len() - pattern.len() {
+        if &zip_binary[offset..offset + pattern.len()] == pattern {
+            zip_binary[offset..offset + pattern.len()].copy_from_slice(replacement);
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_entry_matching_pattern() {
+    let directory_object = Object::new();
+    let folder_object = Object::new();
+
+    Reflect::set(&folder_object, &JsValue::from("manifest.json"), &JsValue::from("{}")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("MyFolder"), &folder_object).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+
+    let manifest = extract_zip_entry(Uint8Array::from(&zip_binary[..]), "**/manifest.json").ok().unwrap();
+
+    assert_eq!(b"{}".to_vec(), manifest.to_vec());
+    assert!(extract_zip_entry(Uint8Array::from(&zip_binary[..]), "manifest.json").is_err());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_entry_prefers_exact_name_then_first_match() {
+    let directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("whatX.txt"), &JsValue::from("first")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("what?.txt"), &JsValue::from("literal")).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+
+    let literal_file = extract_zip_entry(Uint8Array::from(&zip_binary[..]), "what?.txt").ok().unwrap();
+    let first_file = extract_zip_entry(Uint8Array::from(&zip_binary[..]), "what*.txt").ok().unwrap();
+
+    assert_eq!(b"literal".to_vec(), literal_file.to_vec());
+    assert_eq!(b"first".to_vec(), first_file.to_vec());
+}
+
+#[wasm_bindgen_test]
+fn extract_zip_entry_too_large_for_memory() {
+    let directory_object = Object::new();
+    Reflect::set(&directory_object, &JsValue::from("Large.bin"), &JsValue::from("yow")).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &JsValue::from("forceZip64"), &JsValue::from(true)).unwrap();
+
+    let mut zip_binary = generate_zip_binary_with_options(directory_object, options).ok().unwrap().into_vec();
+
+    //The uncompressed size is the first value of the ZIP64 extra field, which follows the file name in the central directory
+    let central_directory_offset = zip_binary.windows(4).position(|window| window == [0x50, 0x4B, 0x01, 0x02]).unwrap();
+    let uncompressed_size_offset = central_directory_offset + 46 + "Large.bin".len() + 4;
+    zip_binary[uncompressed_size_offset..uncompressed_size_offset + 8].copy_from_slice(&0x100000000u64.to_le_bytes());
+
+    let zip_error = extract_zip_entry(Uint8Array::from(zip_binary.as_slice()), "Large.bin").err().unwrap();
+    let message = Reflect::get(&JsValue::from(zip_error), &JsValue::from("message")).unwrap();
+
+    assert_eq!(Some(String::from("The file Large.bin is too large to be extracted into memory")), message.as_string());
+}
+
+#[wasm_bindgen_test]
+fn append_zip_binary_to_generated_archive() {
+    let directory_object = Object::new();
+    let appended_directory_object = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&appended_directory_object, &JsValue::from("Bye.txt"), &JsValue::from("Bye!")).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let appended_zip_binary = append_zip_binary(Uint8Array::from(&zip_binary[..]), appended_directory_object).ok().unwrap();
+
+    let zip_entries = list_zip_entries(Uint8Array::from(&appended_zip_binary[..])).ok().unwrap();
+
+    assert_eq!(2, zip_entries.length());
+    assert_eq!(&zip_binary[..45], &appended_zip_binary[..45]);
+}
+
+#[wasm_bindgen_test]
+fn append_zip_binary_keeps_archive_comment_and_rejects_existing_names() {
+    let directory_object = Object::new();
+    let appended_directory_object = Object::new();
+    let options = Object::new();
+    let appended_options = Object::new();
+
+    Reflect::set(&directory_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&appended_directory_object, &JsValue::from("Bye.txt"), &JsValue::from("Bye!")).unwrap();
+    Reflect::set(&options, &JsValue::from("comment"), &JsValue::from("Capoo archive")).unwrap();
+    Reflect::set(&appended_options, &JsValue::from("comment"), &JsValue::from("BugCat archive")).unwrap();
+
+    let zip_binary = generate_zip_binary_with_options(directory_object.clone(), options).ok().unwrap();
+
+    let appended_zip_binary = append_zip_binary(Uint8Array::from(&zip_binary[..]), appended_directory_object.clone()).ok().unwrap();
+    assert_eq!("Capoo archive", ZipArchive::new(&appended_zip_binary).unwrap().get_comment());
+
+    let appended_zip_binary = append_zip_binary_with_options(Uint8Array::from(&zip_binary[..]), appended_directory_object, appended_options).ok().unwrap();
+    assert_eq!("BugCat archive", ZipArchive::new(&appended_zip_binary).unwrap().get_comment());
+
+    assert!(append_zip_binary(Uint8Array::from(&zip_binary[..]), directory_object).is_err());
+}
+
+#[wasm_bindgen_test]
+fn delete_and_rename_zip_entries() {
+    let directory_object = Object::new();
+    let folder_object = Object::new();
+
+    Reflect::set(&folder_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&folder_object, &JsValue::from(".DS_Store"), &JsValue::from("")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("MyFolder"), &folder_object).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let zip_binary = delete_zip_entries(Uint8Array::from(&zip_binary[..]), "**/.DS_Store").ok().unwrap();
+    let zip_binary = rename_zip_entries(Uint8Array::from(&zip_binary[..]), "MyFolder", "Renamed").ok().unwrap();
+
+    let zip_entries = list_zip_entries(Uint8Array::from(&zip_binary[..])).ok().unwrap();
+
+    assert_eq!(2, zip_entries.length());
+    assert_eq!(Some(String::from("Renamed/Hello.txt")), Reflect::get(&zip_entries.get(1), &JsValue::from("name")).unwrap().as_string());
+    assert!(rename_zip_entries(Uint8Array::from(&zip_binary[..]), "MyFolder", "Renamed").is_err());
+}
+
+#[wasm_bindgen_test]
+fn rename_zip_entries_keeps_folders_and_rejects_taken_names() {
+    let directory_object = Object::new();
+    let folder_object = Object::new();
+
+    Reflect::set(&folder_object, &JsValue::from("Hello.txt"), &JsValue::from("Hello!")).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("MyFolder"), &folder_object).unwrap();
+    Reflect::set(&directory_object, &JsValue::from("Bye.txt"), &JsValue::from("Bye!")).unwrap();
+
+    let zip_binary = generate_zip_binary(directory_object).ok().unwrap();
+    let renamed_zip_binary = rename_zip_entries(Uint8Array::from(&zip_binary[..]), "MyFolder/", "Renamed").ok().unwrap();
+
+    let file_names: Vec<String> = ZipArchive::new(&renamed_zip_binary).unwrap().get_entries().iter()
+        .map(|zip_archive_entry| zip_archive_entry.file_name.clone())
+        .collect();
+
+    assert_eq!(vec!["Renamed/", "Renamed/Hello.txt", "Bye.txt"], file_names);
+
+    assert!(rename_zip_entries(Uint8Array::from(&zip_binary[..]), "Bye.txt", "MyFolder").is_err());
+    assert!(rename_zip_entries(Uint8Array::from(&zip_binary[..]), "Bye.txt", "MyFolder/Hello.txt").is_err());
+    assert!(rename_zip_entries(Uint8Array::from(&zip_binary[..]), "Bye.txt", "").is_err());
+    assert!(rename_zip_entries(Uint8Array::from(&zip_binary[..]), "Bye.txt", "/Bye.txt").is_err());
 }
\ No newline at end of file